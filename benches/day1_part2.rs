@@ -0,0 +1,64 @@
+// Day 1 part 2's single-pass matcher against searching every line once per pattern, on a big
+// made-up input. No bench crates, so it's just the best of a few timed runs:
+//
+//   cargo bench --bench day1_part2
+
+use rs_advent_of_code_2023::day1::part2;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const LINES: usize = 200_000;
+const RUNS: usize = 5;
+
+fn main() {
+    for length in [8, 40, 200] {
+        let lines = synthetic_lines(LINES, length);
+        let matcher = best_of(&lines, part2::parse_calibration_value);
+        let search = best_of(&lines, part2::parse_calibration_value_by_search);
+
+        println!(
+            "{} lines of {} bytes: matcher {:?}, search {:?} ({:.1}x)",
+            LINES,
+            length,
+            matcher,
+            search,
+            search.as_secs_f64() / matcher.as_secs_f64()
+        );
+    }
+}
+
+fn best_of<E>(lines: &[String], parse: fn(&str) -> Result<u64, E>) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start_time = Instant::now();
+            let sum = lines
+                .iter()
+                .filter_map(|line| parse(black_box(line)).ok())
+                .sum::<u64>();
+            black_box(sum);
+            start_time.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+// Mostly letters from the digit words, so there are plenty of near misses like "thre" or "ni",
+// with the odd digit thrown in.
+fn synthetic_lines(count: usize, length: usize) -> Vec<String> {
+    let alphabet = b"onetwhrfuivsxgzabcdklmpqy0123456789";
+    let mut state = 0x2023_1201_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..count)
+        .map(|_| {
+            (0..length)
+                .map(|_| alphabet[next() as usize % alphabet.len()] as char)
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error as IoError};
+
+pub const ANSWERS_PATH: &str = "inputs/answers.txt";
+
+// The known-good answers, keyed by (input file, day, part). Each line of the file looks like
+// `inputs/day5.txt 5.2 11611182`, and lines starting with `#` are comments.
+#[derive(Debug, PartialEq)]
+pub struct Answers(HashMap<(String, usize, usize), String>);
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    InvalidLine(String),
+    IoError(IoError),
+}
+
+impl TryFrom<File> for Answers {
+    type Error = AnswersError;
+
+    fn try_from(value: File) -> Result<Self, Self::Error> {
+        let buf = BufReader::new(value);
+        let lines = buf.lines().collect::<Result<Vec<_>, _>>()?;
+        Answers::from_lines(lines.iter().map(|line| &line[..]))
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = AnswersError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Answers::from_lines(value.lines())
+    }
+}
+
+impl Answers {
+    pub fn verify(&self, input_path: &str, day: usize, part: usize, answer: &str) -> Verdict {
+        match self.0.get(&(String::from(input_path), day, part)) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Missing,
+        }
+    }
+
+    fn from_lines<'a, Iter: Iterator<Item = &'a str>>(lines: Iter) -> Result<Self, AnswersError> {
+        let mut answers = HashMap::new();
+
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || AnswersError::InvalidLine(String::from(line));
+            let mut parts = line.split_whitespace();
+            let input_path = parts.next().ok_or_else(invalid)?;
+            let (day, part) = parts
+                .next()
+                .and_then(|puzzle| puzzle.split_once('.'))
+                .ok_or_else(invalid)?;
+            let day = day.parse::<usize>().map_err(|_| invalid())?;
+            let part = part.parse::<usize>().map_err(|_| invalid())?;
+            let answer = parts.next().ok_or_else(invalid)?;
+            if parts.next().is_some() {
+                return Err(invalid());
+            }
+
+            answers.insert((String::from(input_path), day, part), String::from(answer));
+        }
+
+        Ok(Answers(answers))
+    }
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "Invalid answer line \"{}\"", line),
+            Self::IoError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl From<IoError> for AnswersError {
+    fn from(value: IoError) -> Self {
+        Self::IoError(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, AnswersError, Verdict};
+
+    const ANSWERS: &str = "# Comments are fine.\ninputs/day1.txt 1.1 54667\n\ninputs/day1.txt 1.2 54203\nsomeone-else/day1.txt 1.1 142\n";
+
+    #[test]
+    fn verifies_answers() {
+        let answers: Answers = ANSWERS.try_into().unwrap();
+        assert_eq!(
+            answers.verify("inputs/day1.txt", 1, 1, "54667"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify("inputs/day1.txt", 1, 2, "281"),
+            Verdict::Fail(String::from("54203"))
+        );
+        assert_eq!(
+            answers.verify("inputs/day2.txt", 2, 1, "8"),
+            Verdict::Missing
+        );
+    }
+
+    #[test]
+    fn keys_by_input_file() {
+        let answers: Answers = ANSWERS.try_into().unwrap();
+        assert_eq!(
+            answers.verify("someone-else/day1.txt", 1, 1, "142"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.verify("someone-else/day1.txt", 1, 2, "281"),
+            Verdict::Missing
+        );
+    }
+
+    #[test]
+    fn errors_on_bad_line() {
+        let result: Result<Answers, AnswersError> = "inputs/day1.txt one 54667".try_into();
+        match result {
+            Err(AnswersError::InvalidLine(line)) => assert_eq!(line, "inputs/day1.txt one 54667"),
+            Ok(answers) => panic!("Got success {:?}", answers),
+            Err(err) => panic!("Got wrong error {}", err),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use rs_advent_of_code_2023::runner::{Failure, Task};
+use std::time::{Duration, Instant};
+
+pub const TABLE_HEADER: &str = "| Puzzle | Parse min | Parse median | Parse mean | Parse p95 | Solve min | Solve median | Solve mean | Solve p95 |\n|---|--:|--:|--:|--:|--:|--:|--:|--:|";
+
+// Parse and solve timings for one puzzle over every iteration.
+pub struct Bench {
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+pub fn bench(task: &Task, iterations: usize) -> Result<Bench, Failure> {
+    let mut parse_times = Vec::<Duration>::with_capacity(iterations);
+    let mut solve_times = Vec::<Duration>::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        // Opening the file isn't part of parsing, so it stays outside the timer.
+        let mut reader = task.input.open().map_err(Failure::Io)?;
+
+        let start_time = Instant::now();
+        let input = task.puzzle.parse(&mut reader).map_err(Failure::Parse)?;
+        parse_times.push(start_time.elapsed());
+
+        let start_time = Instant::now();
+        task.puzzle.solve(input).map_err(Failure::Solve)?;
+        solve_times.push(start_time.elapsed());
+    }
+
+    Ok(Bench {
+        parse: Stats::from(parse_times),
+        solve: Stats::from(solve_times),
+    })
+}
+
+impl From<Vec<Duration>> for Stats {
+    fn from(mut value: Vec<Duration>) -> Self {
+        value.sort();
+
+        let len = value.len();
+        let median = if len.is_multiple_of(2) {
+            (value[len / 2 - 1] + value[len / 2]) / 2
+        } else {
+            value[len / 2]
+        };
+        // Nearest-rank percentile, so it's always one of the actual samples.
+        let p95 = value[(len * 95).div_ceil(100) - 1];
+
+        Stats {
+            min: value[0],
+            median,
+            mean: value.iter().sum::<Duration>() / len as u32,
+            p95,
+        }
+    }
+}
+
+// One row of a Markdown table, so results can be pasted side by side before and after a change.
+pub fn table_row(name: &str, bench: &Bench) -> String {
+    format!(
+        "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+        name,
+        format_duration(bench.parse.min),
+        format_duration(bench.parse.median),
+        format_duration(bench.parse.mean),
+        format_duration(bench.parse.p95),
+        format_duration(bench.solve.min),
+        format_duration(bench.solve.median),
+        format_duration(bench.solve.mean),
+        format_duration(bench.solve.p95),
+    )
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_duration, Stats};
+    use std::time::Duration;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values
+            .iter()
+            .map(|value| Duration::from_micros(*value))
+            .collect()
+    }
+
+    #[test]
+    fn calculates_stats() {
+        let stats = Stats::from(micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(
+            stats,
+            Stats {
+                min: Duration::from_micros(1),
+                median: Duration::from_micros(3),
+                mean: Duration::from_micros(3),
+                p95: Duration::from_micros(5),
+            }
+        );
+    }
+
+    #[test]
+    fn median_of_even_samples_is_averaged() {
+        let stats = Stats::from(micros(&[1, 2, 3, 10]));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+    }
+
+    #[test]
+    fn p95_ignores_the_worst_outliers() {
+        let mut samples = micros(&[1; 99]);
+        samples.push(Duration::from_secs(1));
+        let stats = Stats::from(samples);
+        assert_eq!(stats.p95, Duration::from_micros(1));
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(812)), "812 ns");
+        assert_eq!(format_duration(Duration::from_nanos(15_300)), "15.30 µs");
+        assert_eq!(format_duration(Duration::from_micros(2_410)), "2.41 ms");
+        assert_eq!(format_duration(Duration::from_millis(1_200)), "1.20 s");
+    }
+}
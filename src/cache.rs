@@ -0,0 +1,154 @@
+use crate::solution::Puzzle;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub const CACHE_PATH: &str = ".aoc-cache";
+
+// Answers we've already worked out, so running everything again doesn't have to re-solve the
+// puzzles whose input and solver haven't changed. Each line of the file looks like
+// `5.2 9f2c6b0e4d3a1875 1 46`: the puzzle, a hash of its input, the solver's version, and the
+// answer. It's only a cache, so a line that doesn't make sense is just skipped.
+//
+// The runner can look things up from several threads at once, hence the `Mutex`.
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    answers: Mutex<HashMap<Key, String>>,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Key {
+    day: usize,
+    part: usize,
+    hash: u64,
+    version: usize,
+}
+
+impl Key {
+    pub fn new(puzzle: &dyn Puzzle, input: &[u8]) -> Self {
+        Key {
+            day: puzzle.day(),
+            part: puzzle.part(),
+            hash: fnv1a(input),
+            version: puzzle.version(),
+        }
+    }
+}
+
+impl Cache {
+    // A cache that hasn't been saved yet is just empty.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let answers = contents.lines().filter_map(parse_line).collect();
+        Ok(Cache {
+            path,
+            answers: Mutex::new(answers),
+        })
+    }
+
+    pub fn get(&self, key: &Key) -> Option<String> {
+        self.answers.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: Key, answer: String) {
+        // Answers are always one line, but one that isn't would break the file.
+        if !answer.contains('\n') {
+            self.answers.lock().unwrap().insert(key, answer);
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let answers = self.answers.lock().unwrap();
+        let mut lines = answers
+            .iter()
+            .map(|(key, answer)| {
+                format!(
+                    "{}.{} {:016x} {} {}\n",
+                    key.day, key.part, key.hash, key.version, answer
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        fs::write(&self.path, lines.concat())
+    }
+
+    // Throws away everything that's been cached, which is the same as never having cached it.
+    pub fn clear<P: AsRef<Path>>(path: P) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Key, String)> {
+    let mut parts = line.splitn(4, ' ');
+    let (day, part) = parts.next()?.split_once('.')?;
+    let key = Key {
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        hash: u64::from_str_radix(parts.next()?, 16).ok()?,
+        version: parts.next()?.parse().ok()?,
+    };
+    Some((key, String::from(parts.next()?)))
+}
+
+// 64-bit FNV-1a. It's not cryptographic, but it's tiny, and good enough to tell inputs apart.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, Cache, Key};
+    use crate::registry;
+    use std::{env, fs};
+
+    #[test]
+    fn hashes_like_the_reference() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn keys_change_with_the_input() {
+        let puzzle = registry::find(5, 2).unwrap();
+        assert_eq!(Key::new(puzzle, b"1 2 3"), Key::new(puzzle, b"1 2 3"));
+        assert_ne!(Key::new(puzzle, b"1 2 3"), Key::new(puzzle, b"1 2 4"));
+        assert_ne!(
+            Key::new(puzzle, b"1 2 3"),
+            Key::new(registry::find(5, 1).unwrap(), b"1 2 3")
+        );
+    }
+
+    #[test]
+    fn survives_a_round_trip() {
+        let path = env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let key = Key::new(registry::find(5, 2).unwrap(), b"seeds: 1 2");
+
+        let cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get(&key), None);
+        cache.insert(key.clone(), String::from("46"));
+        cache.save().unwrap();
+
+        let cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get(&key).as_deref(), Some("46"));
+
+        Cache::clear(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(Cache::load(&path).unwrap().get(&key), None);
+        fs::remove_file(&path).ok();
+    }
+}
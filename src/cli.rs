@@ -0,0 +1,546 @@
+use crate::report::Format;
+use crate::serve::DEFAULT_PORT;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const USAGE: &str =
+    "Usage: aoc [--all | --list | --verify | --bench | --example | --watch] [SELECTION...]
+       aoc DAY --repl [--input PATH]
+       aoc serve [--port PORT] [SELECTION...]
+
+Selections:
+    5       Every part of day 5
+    5.2     Only part 2 of day 5
+    3-7     Every part of days 3 through 7
+
+With no selections (or with --all), every puzzle is run.
+
+`serve` answers POST /day/{n}/part/{m} on localhost with the input as the body,
+and sends back the answer, timings and any parse error as JSON.
+
+Options:
+    --list              Print the selected puzzles instead of running them
+    --verify            Check every answer against inputs/answers.txt
+    --bench             Time parsing and solving separately over many runs
+    --iterations <N>    How many runs --bench does (default 100)
+    --example           Run the examples from the puzzle descriptions and check their answers
+    --format <FORMAT>   Print results as text (the default), json or csv
+    -j, --jobs <N>      Solve up to N puzzles at once (default 1)
+    -i, --input <PATH>  Use this input instead of inputs/dayN.txt. Can be given more than
+                        once; - reads stdin and a directory means every file in it
+    --timeout <SECS>    Give up on any puzzle that takes longer than this
+    --no-cache          Solve everything fresh instead of reusing answers from .aoc-cache
+    --clear-cache       Throw away every cached answer before running
+    --watch             Solve again whenever an input file changes, until stopped
+    --repl              Load one day's input and answer questions about it
+    --port <PORT>       Which port `serve` listens on (default 2023)";
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub list: bool,
+    pub verify: bool,
+    pub bench: bool,
+    pub example: bool,
+    pub watch: bool,
+    pub repl: bool,
+    pub serve: bool,
+    pub port: u16,
+    pub iterations: usize,
+    pub format: Format,
+    pub jobs: usize,
+    pub inputs: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub no_cache: bool,
+    pub clear_cache: bool,
+    pub help: bool,
+    pub selectors: Vec<Selector>,
+}
+
+// A single "5", "5.2" or "3-7" from the command line.
+#[derive(Debug, PartialEq)]
+pub struct Selector {
+    pub days: RangeInclusive<usize>,
+    pub part: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    InvalidSelector(String),
+    Conflict(String, String),
+    UnknownDay(usize),
+    UnknownPart(usize, usize),
+    NeedsOneDay(String),
+}
+
+impl Args {
+    pub fn parse<Iter: Iterator<Item = String>>(mut args: Iter) -> Result<Self, CliError> {
+        let mut parsed = Args::default();
+        let mut all = false;
+
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "--all" => all = true,
+                "--list" => parsed.list = true,
+                "--verify" => parsed.verify = true,
+                "--bench" => parsed.bench = true,
+                "--example" => parsed.example = true,
+                "--watch" => parsed.watch = true,
+                "--repl" => parsed.repl = true,
+                "serve" => parsed.serve = true,
+                "--port" => parsed.port = parse_value(&arg, args.next())?,
+                "--iterations" => parsed.iterations = parse_value(&arg, args.next())?,
+                "--format" => parsed.format = parse_value(&arg, args.next())?,
+                "-j" | "--jobs" => parsed.jobs = parse_value(&arg, args.next())?,
+                "-i" | "--input" => parsed.inputs.push(parse_value(&arg, args.next())?),
+                "--timeout" => {
+                    let seconds: f64 = parse_value(&arg, args.next())?;
+                    let timeout = Duration::try_from_secs_f64(seconds)
+                        .ok()
+                        .filter(|timeout| !timeout.is_zero())
+                        .ok_or(CliError::InvalidValue(arg, seconds.to_string()))?;
+                    parsed.timeout = Some(timeout);
+                }
+                "--no-cache" => parsed.no_cache = true,
+                "--clear-cache" => parsed.clear_cache = true,
+                "-h" | "--help" => parsed.help = true,
+                flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                selector => parsed.selectors.push(selector.try_into()?),
+            }
+        }
+
+        if parsed.iterations == 0 {
+            return Err(CliError::InvalidValue(
+                String::from("--iterations"),
+                String::from("0"),
+            ));
+        }
+
+        if parsed.jobs == 0 {
+            return Err(CliError::InvalidValue(
+                String::from("--jobs"),
+                String::from("0"),
+            ));
+        }
+
+        // Examples bring their own inputs and answers.
+        if parsed.example {
+            if parsed.verify {
+                return Err(CliError::Conflict(
+                    String::from("--example"),
+                    String::from("--verify"),
+                ));
+            }
+
+            if !parsed.inputs.is_empty() {
+                return Err(CliError::Conflict(
+                    String::from("--example"),
+                    String::from("--input"),
+                ));
+            }
+        }
+
+        // Watching only makes sense for files that can change, and for re-solving them.
+        if parsed.watch {
+            let conflicts = [
+                (parsed.verify, "--verify"),
+                (parsed.bench, "--bench"),
+                (parsed.example, "--example"),
+                (parsed.inputs.iter().any(|input| input == "-"), "--input -"),
+            ];
+            if let Some((_, flag)) = conflicts.iter().find(|(set, _)| *set) {
+                return Err(CliError::Conflict(
+                    String::from("--watch"),
+                    String::from(*flag),
+                ));
+            }
+        }
+
+        // The REPL reads commands from stdin, so its input has to come from a file.
+        if parsed.repl {
+            let conflicts = [
+                (parsed.verify, "--verify"),
+                (parsed.bench, "--bench"),
+                (parsed.example, "--example"),
+                (parsed.watch, "--watch"),
+                (parsed.inputs.iter().any(|input| input == "-"), "--input -"),
+            ];
+            if let Some((_, flag)) = conflicts.iter().find(|(set, _)| *set) {
+                return Err(CliError::Conflict(
+                    String::from("--repl"),
+                    String::from(*flag),
+                ));
+            }
+
+            if all || parsed.repl_day().is_none() || parsed.inputs.len() > 1 {
+                return Err(CliError::NeedsOneDay(String::from("--repl")));
+            }
+        }
+
+        // The server gets its inputs from requests, and only ever solves them.
+        if parsed.serve {
+            let conflicts = [
+                (parsed.list, "--list"),
+                (parsed.verify, "--verify"),
+                (parsed.bench, "--bench"),
+                (parsed.example, "--example"),
+                (parsed.watch, "--watch"),
+                (parsed.repl, "--repl"),
+                (!parsed.inputs.is_empty(), "--input"),
+            ];
+            if let Some((_, flag)) = conflicts.iter().find(|(set, _)| *set) {
+                return Err(CliError::Conflict(
+                    String::from("serve"),
+                    String::from(*flag),
+                ));
+            }
+        }
+
+        // Asking for everything wins over asking for something specific.
+        if all {
+            parsed.selectors.clear();
+        }
+
+        Ok(parsed)
+    }
+
+    // The one day that was selected, if only one was.
+    pub fn repl_day(&self) -> Option<usize> {
+        match &self.selectors[..] {
+            [selector] if selector.days.start() == selector.days.end() => {
+                Some(*selector.days.start())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn includes(&self, day: usize, part: usize) -> bool {
+        self.selectors.is_empty()
+            || self
+                .selectors
+                .iter()
+                .any(|selector| selector.includes(day, part))
+    }
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            list: false,
+            verify: false,
+            bench: false,
+            example: false,
+            watch: false,
+            repl: false,
+            serve: false,
+            port: DEFAULT_PORT,
+            iterations: 100,
+            format: Format::Text,
+            jobs: 1,
+            inputs: vec![],
+            timeout: None,
+            no_cache: false,
+            clear_cache: false,
+            help: false,
+            selectors: vec![],
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, CliError> {
+    let value = value.ok_or(CliError::MissingValue(String::from(flag)))?;
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue(String::from(flag), value))
+}
+
+impl Selector {
+    pub fn includes(&self, day: usize, part: usize) -> bool {
+        self.days.contains(&day) && self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+impl TryFrom<&str> for Selector {
+    type Error = CliError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let invalid = || CliError::InvalidSelector(String::from(value));
+        let parse = |num: &str| num.parse::<usize>().map_err(|_| invalid());
+
+        if let Some((first, last)) = value.split_once('-') {
+            let (first, last) = (parse(first)?, parse(last)?);
+            if first > last {
+                return Err(invalid());
+            }
+
+            Ok(Selector {
+                days: first..=last,
+                part: None,
+            })
+        } else if let Some((day, part)) = value.split_once('.') {
+            let day = parse(day)?;
+            Ok(Selector {
+                days: day..=day,
+                part: Some(parse(part)?),
+            })
+        } else {
+            let day = parse(value)?;
+            Ok(Selector {
+                days: day..=day,
+                part: None,
+            })
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownFlag(flag) => write!(f, "Unknown flag '{}'", flag),
+            Self::MissingValue(flag) => write!(f, "Flag '{}' needs a value", flag),
+            Self::InvalidValue(flag, value) => {
+                write!(f, "Invalid value '{}' for flag '{}'", value, flag)
+            }
+            Self::InvalidSelector(selector) => write!(
+                f,
+                "Invalid selection '{}', expected something like 5, 5.2 or 3-7",
+                selector
+            ),
+            Self::Conflict(first, second) => {
+                write!(
+                    f,
+                    "Flags '{}' and '{}' can't be used together",
+                    first, second
+                )
+            }
+            Self::UnknownDay(day) => write!(f, "There's no solution for day {} (yet!)", day),
+            Self::UnknownPart(day, part) => {
+                write!(f, "There's no solution for day {}, part {}", day, part)
+            }
+            Self::NeedsOneDay(flag) => write!(
+                f,
+                "'{}' needs exactly one day and at most one input, like `aoc 8 {}`",
+                flag, flag
+            ),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, CliError, Duration, Format, Selector};
+
+    fn parse(args: &[&str]) -> Result<Args, CliError> {
+        Args::parse(args.iter().map(|arg| String::from(*arg)))
+    }
+
+    #[test]
+    fn no_arguments_runs_everything() {
+        let args = parse(&[]).unwrap();
+        assert!(args.selectors.is_empty());
+        assert!(args.includes(1, 1));
+        assert!(args.includes(9, 2));
+    }
+
+    #[test]
+    fn parses_a_day() {
+        let selector: Selector = "5".try_into().unwrap();
+        assert_eq!(
+            selector,
+            Selector {
+                days: 5..=5,
+                part: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_day_and_part() {
+        let selector: Selector = "5.2".try_into().unwrap();
+        assert_eq!(
+            selector,
+            Selector {
+                days: 5..=5,
+                part: Some(2)
+            }
+        );
+        assert!(selector.includes(5, 2));
+        assert!(!selector.includes(5, 1));
+    }
+
+    #[test]
+    fn parses_a_range() {
+        let selector: Selector = "3-7".try_into().unwrap();
+        assert!(!selector.includes(2, 1));
+        assert!(selector.includes(3, 1));
+        assert!(selector.includes(7, 2));
+        assert!(!selector.includes(8, 1));
+    }
+
+    #[test]
+    fn errors_on_backwards_range() {
+        let result: Result<Selector, _> = "7-3".try_into();
+        assert_eq!(result, Err(CliError::InvalidSelector(String::from("7-3"))));
+    }
+
+    #[test]
+    fn errors_on_garbage() {
+        assert_eq!(
+            parse(&["five"]),
+            Err(CliError::InvalidSelector(String::from("five")))
+        );
+        assert_eq!(
+            parse(&["--frobnicate"]),
+            Err(CliError::UnknownFlag(String::from("--frobnicate")))
+        );
+    }
+
+    #[test]
+    fn parses_flag_values() {
+        let args = parse(&["--bench", "--iterations", "20", "6"]).unwrap();
+        assert!(args.bench);
+        assert_eq!(args.iterations, 20);
+        assert!(args.includes(6, 1));
+        assert!(!args.includes(5, 1));
+    }
+
+    #[test]
+    fn parses_format() {
+        let args = parse(&["--format", "csv"]).unwrap();
+        assert_eq!(args.format, Format::Csv);
+        assert_eq!(
+            parse(&["--format", "yaml"]),
+            Err(CliError::InvalidValue(
+                String::from("--format"),
+                String::from("yaml")
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_jobs() {
+        assert_eq!(parse(&[]).unwrap().jobs, 1);
+        assert_eq!(parse(&["-j", "4"]).unwrap().jobs, 4);
+        assert_eq!(
+            parse(&["--jobs", "0"]),
+            Err(CliError::InvalidValue(
+                String::from("--jobs"),
+                String::from("0")
+            ))
+        );
+    }
+
+    #[test]
+    fn collects_inputs() {
+        let args = parse(&["5", "-i", "alice.txt", "--input", "-"]).unwrap();
+        assert_eq!(args.inputs, vec!["alice.txt", "-"]);
+    }
+
+    #[test]
+    fn parses_timeout() {
+        assert_eq!(parse(&[]).unwrap().timeout, None);
+        assert_eq!(
+            parse(&["--timeout", "1.5"]).unwrap().timeout,
+            Some(Duration::from_millis(1500))
+        );
+        assert!(parse(&["--timeout", "0"]).is_err());
+        assert!(parse(&["--timeout", "-3"]).is_err());
+    }
+
+    #[test]
+    fn parses_cache_flags() {
+        let args = parse(&[]).unwrap();
+        assert!(!args.no_cache && !args.clear_cache);
+        let args = parse(&["--no-cache", "--clear-cache"]).unwrap();
+        assert!(args.no_cache && args.clear_cache);
+    }
+
+    #[test]
+    fn watches_files_only() {
+        assert!(parse(&["8", "--watch", "-i", "scratch.txt"]).unwrap().watch);
+        assert_eq!(
+            parse(&["--watch", "-i", "-"]),
+            Err(CliError::Conflict(
+                String::from("--watch"),
+                String::from("--input -")
+            ))
+        );
+        assert!(parse(&["--watch", "--verify"]).is_err());
+        assert!(parse(&["--watch", "--bench"]).is_err());
+        assert!(parse(&["--example", "--watch"]).is_err());
+    }
+
+    #[test]
+    fn repl_takes_one_day() {
+        let args = parse(&["8", "--repl"]).unwrap();
+        assert_eq!(args.repl_day(), Some(8));
+        assert!(parse(&["--repl", "5.2", "-i", "alice.txt"]).is_ok());
+        let needs_one_day = Err(CliError::NeedsOneDay(String::from("--repl")));
+        assert_eq!(parse(&["--repl"]), needs_one_day);
+        assert_eq!(parse(&["3-7", "--repl"]), needs_one_day);
+        assert_eq!(parse(&["3", "4", "--repl"]), needs_one_day);
+        assert_eq!(parse(&["3", "--repl", "-i", "a", "-i", "b"]), needs_one_day);
+        assert!(parse(&["3", "--repl", "-i", "-"]).is_err());
+        assert!(parse(&["3", "--repl", "--watch"]).is_err());
+    }
+
+    #[test]
+    fn parses_serve() {
+        let args = parse(&["serve", "--port", "8080", "5"]).unwrap();
+        assert!(args.serve);
+        assert_eq!(args.port, 8080);
+        assert_eq!(args.selectors.len(), 1);
+        assert_eq!(parse(&["serve"]).unwrap().port, 2023);
+        assert_eq!(
+            parse(&["serve", "-i", "alice.txt"]),
+            Err(CliError::Conflict(
+                String::from("serve"),
+                String::from("--input")
+            ))
+        );
+        assert!(parse(&["serve", "--verify"]).is_err());
+        assert!(parse(&["serve", "--port", "99999"]).is_err());
+    }
+
+    #[test]
+    fn example_brings_its_own_input() {
+        assert!(parse(&["7", "--example"]).unwrap().example);
+        assert!(parse(&["--example", "--bench"]).is_ok());
+        assert_eq!(
+            parse(&["--example", "-i", "alice.txt"]),
+            Err(CliError::Conflict(
+                String::from("--example"),
+                String::from("--input")
+            ))
+        );
+        assert!(parse(&["--verify", "--example"]).is_err());
+    }
+
+    #[test]
+    fn errors_on_bad_flag_values() {
+        assert_eq!(
+            parse(&["--iterations"]),
+            Err(CliError::MissingValue(String::from("--iterations")))
+        );
+        assert_eq!(
+            parse(&["--iterations", "lots"]),
+            Err(CliError::InvalidValue(
+                String::from("--iterations"),
+                String::from("lots")
+            ))
+        );
+    }
+
+    #[test]
+    fn all_overrides_selections() {
+        let args = parse(&["5", "--all"]).unwrap();
+        assert!(args.includes(1, 1));
+    }
+}
@@ -0,0 +1,209 @@
+use super::explain::{Explanation, Token};
+use super::matcher::{Match, Matcher};
+use super::vocabulary::{Vocabulary, DIGITS};
+use super::{Day1Error, State};
+use crate::input;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::io::BufRead;
+
+// How a line's tokens turn into its calibration value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rule {
+    // The first token and the last one, like the puzzle says. One token is both.
+    FirstAndLast,
+    // The first N tokens, in order. A line with fewer than N is an error.
+    First(usize),
+    // Every token on the line, in order.
+    All,
+}
+
+// Reads calibration values out of lines. Which tokens count comes from a vocabulary (just the
+// digits for part 1, English words too for part 2, or anything else), and how they're put
+// together comes from a rule. Both parts are one of these with different settings.
+pub struct Calibrator {
+    matcher: Matcher,
+    ignore_case: bool,
+    rule: Rule,
+}
+
+impl Calibrator {
+    pub fn new(vocabulary: &Vocabulary, rule: Rule) -> Self {
+        let words = vocabulary
+            .words()
+            .iter()
+            .map(|(word, value)| (fold(word, vocabulary.ignores_case()).into_owned(), *value))
+            .collect::<Vec<_>>();
+        let patterns = DIGITS
+            .iter()
+            .copied()
+            .chain(words.iter().map(|(word, value)| (&word[..], *value)))
+            .collect::<Vec<_>>();
+
+        Calibrator {
+            matcher: Matcher::new(&patterns),
+            ignore_case: vocabulary.ignores_case(),
+            rule,
+        }
+    }
+
+    pub fn part1() -> Self {
+        Self::new(&Vocabulary::digits(), Rule::FirstAndLast)
+    }
+
+    pub fn part2() -> Self {
+        Self::new(&Vocabulary::english(), Rule::FirstAndLast)
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn value(&self, line: &str) -> Result<u64, Day1Error> {
+        let line = fold(line, self.ignore_case);
+        match self.rule {
+            // The common case doesn't need to hang on to every token.
+            Rule::FirstAndLast => {
+                let (first, last) = self
+                    .matcher
+                    .first_and_last(&line)
+                    .ok_or(Day1Error::NoDigits)?;
+                Ok(first.value as u64 * 10 + last.value as u64)
+            }
+            Rule::First(count) => {
+                let tokens = self.tokens(&line);
+                if tokens.is_empty() {
+                    return Err(Day1Error::NoDigits);
+                }
+                if tokens.len() < count {
+                    return Err(Day1Error::TooFewDigits(count, tokens.len()));
+                }
+                concatenate(&tokens[..count])
+            }
+            Rule::All => {
+                let tokens = self.tokens(&line);
+                if tokens.is_empty() {
+                    return Err(Day1Error::NoDigits);
+                }
+                concatenate(&tokens)
+            }
+        }
+    }
+
+    pub fn sum(&self, state: &State) -> Result<u64, Day1Error> {
+        state.0.iter().try_fold(0, |sum: u64, line| {
+            sum.checked_add(self.value(line)?)
+                .ok_or(Day1Error::ValueTooLarge)
+        })
+    }
+
+    // Sums every line's value as it's read, so only one line is ever in memory.
+    pub fn sum_lines<R: BufRead>(&self, reader: R) -> Result<u64, Day1Error> {
+        input::records(reader).try_fold(0, |sum: u64, (_, line)| {
+            sum.checked_add(self.value(&line?)?)
+                .ok_or(Day1Error::ValueTooLarge)
+        })
+    }
+
+    // Every token in the line, in order. Tokens can overlap ("eightwo" is eight then two), but
+    // one that's entirely inside an earlier one doesn't count, so "viii" is just eight. Offsets
+    // are in the lowercased line when case is ignored.
+    pub fn tokens(&self, line: &str) -> Vec<Match> {
+        let mut matches = self.matcher.matches(line).collect::<Vec<_>>();
+        matches.sort_by_key(|found| (found.start, Reverse(found.end)));
+
+        let mut end = 0;
+        matches.retain(|found| {
+            let keep = found.end > end;
+            end = end.max(found.end);
+            keep
+        });
+        matches
+    }
+
+    // Which tokens the first and last digits were, whatever the rule.
+    pub fn explain(&self, line: &str) -> Result<Explanation, Day1Error> {
+        let line = fold(line, self.ignore_case);
+        let (first, last) = self
+            .matcher
+            .first_and_last(&line)
+            .ok_or(Day1Error::NoDigits)?;
+        Ok(Explanation {
+            first: Token::new(&line, first),
+            last: Token::new(&line, last),
+        })
+    }
+}
+
+// Tokens as the digits of one number, so [4, 2] is 42.
+fn concatenate(tokens: &[Match]) -> Result<u64, Day1Error> {
+    tokens.iter().try_fold(0, |value: u64, token| {
+        value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(token.value as u64))
+            .ok_or(Day1Error::ValueTooLarge)
+    })
+}
+
+// Lowercasing can change how long a line is in bytes (not for anything in the built-in
+// vocabularies, but it can), so matches are only ever looked up in the folded line.
+fn fold(text: &str, ignore_case: bool) -> Cow<'_, str> {
+    if ignore_case {
+        Cow::Owned(text.to_lowercase())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calibrator, Day1Error, Rule, Vocabulary};
+    use crate::day1::State;
+    use crate::input::PuzzleInput;
+
+    fn values(calibrator: &Calibrator, line: &str) -> Vec<usize> {
+        calibrator
+            .tokens(line)
+            .iter()
+            .map(|token| token.value)
+            .collect()
+    }
+
+    #[test]
+    fn finds_tokens_in_order() {
+        let part2 = Calibrator::part2();
+        assert_eq!(values(&part2, "eightwo3"), [8, 2, 3]);
+        assert_eq!(values(&part2, "xtwone3four"), [2, 1, 3, 4]);
+        assert_eq!(values(&Calibrator::part1(), "xtwone3four"), [3]);
+
+        let roman = Calibrator::new(&Vocabulary::built_in("roman").unwrap(), Rule::All);
+        assert_eq!(values(&roman, "viii"), [8]);
+        assert_eq!(values(&roman, "xivx2"), [4, 2]);
+    }
+
+    #[test]
+    fn applies_rules() {
+        let english = Vocabulary::english();
+        let first_two = Calibrator::new(&english, Rule::First(2));
+        assert_eq!(first_two.value("a1twothree4"), Ok(12));
+        assert_eq!(first_two.value("seven"), Err(Day1Error::TooFewDigits(2, 1)));
+        assert_eq!(first_two.value("ninenine"), Ok(99));
+
+        let all = Calibrator::new(&english, Rule::All);
+        assert_eq!(all.value("a1twothree4"), Ok(1234));
+        assert_eq!(all.value("xyz"), Err(Day1Error::NoDigits));
+        assert_eq!(all.value(&"9".repeat(20)), Err(Day1Error::ValueTooLarge));
+
+        let first_and_last = Calibrator::new(&Vocabulary::digits(), Rule::FirstAndLast);
+        assert_eq!(first_and_last.value("a1twothree4"), Ok(14));
+        assert_eq!(first_and_last.rule(), Rule::FirstAndLast);
+    }
+
+    #[test]
+    fn both_parts_are_configurations() {
+        let state =
+            State::from_reader("two1nine\n3eightwothree\nabcone2threexyz\n".as_bytes()).unwrap();
+        assert_eq!(Calibrator::part1().sum(&state), Ok(11 + 33 + 22));
+        assert_eq!(Calibrator::part2().sum(&state), Ok(29 + 33 + 13));
+    }
+}
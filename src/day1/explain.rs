@@ -0,0 +1,154 @@
+use super::matcher::Match;
+use super::{part1, part2, Day1Error};
+use std::fmt::Display;
+
+// Which two tokens a line's calibration value came from, for working out why a sum is wrong.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    pub first: Token,
+    pub last: Token,
+}
+
+// A digit or word found in a line, with where it was as byte offsets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub value: usize,
+    pub kind: Kind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Digit,
+    Word,
+}
+
+// Both parts' takes on one line.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub part1: Result<Explanation, Day1Error>,
+    pub part2: Result<Explanation, Day1Error>,
+}
+
+impl Explanation {
+    pub fn value(&self) -> usize {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+impl Token {
+    // `found` has to be a match in `line`, which always lands on character boundaries since the
+    // patterns are strings too.
+    pub fn new(line: &str, found: Match) -> Self {
+        let text = &line[found.start..found.end];
+        let kind = if text.len() == 1 && text.as_bytes()[0].is_ascii_digit() {
+            Kind::Digit
+        } else {
+            Kind::Word
+        };
+        Token {
+            text: String::from(text),
+            start: found.start,
+            end: found.end,
+            value: found.value,
+            kind,
+        }
+    }
+}
+
+// Part 1 only looks at written digits.
+pub fn part1(line: &str) -> Result<Explanation, Day1Error> {
+    part1::calibrator().explain(line)
+}
+
+pub fn part2(line: &str) -> Result<Explanation, Day1Error> {
+    part2::calibrator().explain(line)
+}
+
+pub fn compare(line: &str) -> Comparison {
+    Comparison {
+        part1: part1(line),
+        part2: part2(line),
+    }
+}
+
+impl Comparison {
+    // Whether the parts came up with different values, counting an error as a value.
+    pub fn disagrees(&self) -> bool {
+        match (&self.part1, &self.part2) {
+            (Ok(part1), Ok(part2)) => part1.value() != part2.value(),
+            (Err(part1), Err(part2)) => part1 != part2,
+            _ => true,
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Digit => write!(f, "digit"),
+            Self::Word => write!(f, "word"),
+        }
+    }
+}
+
+// Like "'two' (word, bytes 0..3)".
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' ({}, bytes {}..{})",
+            self.text, self.kind, self.start, self.end
+        )
+    }
+}
+
+// Like "29 from 'two' (word, bytes 0..3) and 'nine' (word, bytes 4..8)".
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} from {} and {}", self.value(), self.first, self.last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, part1, part2, Day1Error, Kind};
+
+    #[test]
+    fn explains_part1() {
+        let explanation = part1("pqr3stu8vwx").unwrap();
+        assert_eq!(explanation.value(), 38);
+        assert_eq!((explanation.first.start, explanation.first.end), (3, 4));
+        assert_eq!(explanation.last.start, 7);
+        assert_eq!(explanation.first.kind, Kind::Digit);
+        assert_eq!(
+            part1("treb7uchet").unwrap().to_string(),
+            "77 from '7' (digit, bytes 4..5) and '7' (digit, bytes 4..5)"
+        );
+        // Other scripts' digits aren't what the elves meant.
+        assert_eq!(part1("٣"), Err(Day1Error::NoDigits));
+    }
+
+    #[test]
+    fn explains_part2() {
+        assert_eq!(
+            part2("two1nine").unwrap().to_string(),
+            "29 from 'two' (word, bytes 0..3) and 'nine' (word, bytes 4..8)"
+        );
+        let explanation = part2("xeightwo3").unwrap();
+        assert_eq!(explanation.first.text, "eight");
+        assert_eq!(explanation.last.kind, Kind::Digit);
+        assert_eq!(explanation.value(), 83);
+    }
+
+    #[test]
+    fn finds_disagreements() {
+        assert!(!compare("1abc2").disagrees());
+        assert!(compare("two1nine").disagrees());
+        assert!(!compare("nodigits1").disagrees());
+        assert!(compare("one").disagrees());
+        assert!(!compare("xyz").disagrees());
+    }
+}
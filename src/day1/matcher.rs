@@ -0,0 +1,167 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+// Finds every pattern in a line in one left-to-right pass, instead of searching the line once per
+// pattern. It's a trie of the patterns with failure links (Aho-Corasick), flattened into a table
+// so each byte of the line is a single lookup. Matches can overlap, so "eightwo" finds both
+// "eight" and "two".
+pub struct Matcher {
+    transitions: Vec<[usize; 256]>,
+    // Every pattern that ends at a node, including the ones that are only a suffix of it, as
+    // (length in bytes, value).
+    outputs: Vec<Vec<(usize, usize)>>,
+}
+
+// One pattern found in a line, by byte offsets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: usize,
+}
+
+const ROOT: usize = 0;
+
+impl Matcher {
+    pub fn new(patterns: &[(&str, usize)]) -> Self {
+        let mut transitions = vec![[ROOT; 256]];
+        let mut outputs = vec![vec![]];
+        // Whether a node's transition was put there by the trie, rather than left pointing at
+        // the root for now.
+        let mut in_trie = vec![[false; 256]];
+
+        for (pattern, value) in patterns {
+            let mut node = ROOT;
+            for &byte in pattern.as_bytes() {
+                if !in_trie[node][byte as usize] {
+                    transitions.push([ROOT; 256]);
+                    outputs.push(vec![]);
+                    in_trie.push([false; 256]);
+                    transitions[node][byte as usize] = transitions.len() - 1;
+                    in_trie[node][byte as usize] = true;
+                }
+                node = transitions[node][byte as usize];
+            }
+            if !pattern.is_empty() {
+                outputs[node].push((pattern.len(), *value));
+            }
+        }
+
+        // Breadth first, so a node's failure link (the longest suffix of it that's also in the
+        // trie) is always finished before the node is. Bytes that fall off the trie go wherever
+        // the failure link would, which turns the trie into a plain state machine.
+        let mut failures = vec![ROOT; transitions.len()];
+        let mut queue = (0..256)
+            .filter(|&byte| in_trie[ROOT][byte])
+            .map(|byte| transitions[ROOT][byte])
+            .collect::<VecDeque<_>>();
+        while let Some(node) = queue.pop_front() {
+            let inherited = outputs[failures[node]].clone();
+            outputs[node].extend(inherited);
+
+            for byte in 0..256 {
+                let fallback = transitions[failures[node]][byte];
+                if in_trie[node][byte] {
+                    let child = transitions[node][byte];
+                    failures[child] = fallback;
+                    queue.push_back(child);
+                } else {
+                    transitions[node][byte] = fallback;
+                }
+            }
+        }
+
+        Matcher {
+            transitions,
+            outputs,
+        }
+    }
+
+    // Every match in the line, in the order they end.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(ROOT, move |node, (index, byte)| {
+                *node = self.transitions[*node][byte as usize];
+                Some((index + 1, &self.outputs[*node]))
+            })
+            .flat_map(|(end, outputs)| {
+                outputs.iter().map(move |&(length, value)| Match {
+                    start: end - length,
+                    end,
+                    value,
+                })
+            })
+    }
+
+    // The match that starts first and the one that ends last. When two start (or end) in the same
+    // place, the longer one wins, so "viii" is eight from either end rather than ending in "i".
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        let mut matches = self.matches(line);
+        let first = matches.next()?;
+        Some(matches.fold((first, first), |(first, last), found| {
+            let first = if (found.start, Reverse(found.end)) < (first.start, Reverse(first.end)) {
+                found
+            } else {
+                first
+            };
+            let last = if (found.end, Reverse(found.start)) > (last.end, Reverse(last.start)) {
+                found
+            } else {
+                last
+            };
+            (first, last)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Match, Matcher};
+
+    fn matcher() -> Matcher {
+        Matcher::new(&[("one", 1), ("two", 2), ("eight", 8), ("8", 8), ("ne", 0)])
+    }
+
+    fn values(matcher: &Matcher, line: &str) -> Vec<usize> {
+        matcher.matches(line).map(|found| found.value).collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let matcher = matcher();
+        assert_eq!(values(&matcher, "eightwo"), [8, 2]);
+        assert_eq!(values(&matcher, "oneight"), [1, 0, 8]);
+        assert_eq!(values(&matcher, "xx8x"), [8]);
+        assert!(values(&matcher, "tw nothing").is_empty());
+        assert_eq!(
+            matcher.matches("eightwo").last(),
+            Some(Match {
+                start: 4,
+                end: 7,
+                value: 2
+            })
+        );
+    }
+
+    #[test]
+    fn finds_first_and_last() {
+        let matcher = matcher();
+        let (first, last) = matcher.first_and_last("oneight").unwrap();
+        assert_eq!((first.value, last.value), (1, 8));
+        let (first, last) = matcher.first_and_last("8").unwrap();
+        assert_eq!((first.value, last.value), (8, 8));
+        assert_eq!(matcher.first_and_last("nope"), None);
+    }
+
+    #[test]
+    fn prefers_longer_matches_at_either_end() {
+        let matcher = Matcher::new(&[("i", 1), ("iv", 4), ("v", 5)]);
+        let (first, last) = matcher.first_and_last("iv").unwrap();
+        assert_eq!((first.value, last.value), (4, 4));
+        let (first, last) = matcher.first_and_last("viv").unwrap();
+        assert_eq!((first.value, last.value), (5, 4));
+        let (first, last) = matcher.first_and_last("vi i").unwrap();
+        assert_eq!((first.value, last.value), (5, 1));
+    }
+}
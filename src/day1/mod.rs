@@ -1,40 +1,130 @@
+pub mod engine;
+pub mod explain;
+pub mod matcher;
 pub mod part1;
 pub mod part2;
+pub mod query;
+pub mod vocabulary;
 
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead};
 
+pub const TITLE: &str = "Trebuchet?!";
+
+#[derive(Debug)]
 pub struct State(Vec<String>);
 
-#[derive(Debug, PartialEq)]
+impl State {
+    pub fn lines(&self) -> &[String] {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
 pub enum Day1Error {
     NoDigits,
+    InvalidWord(usize, String),
+    TooFewDigits(usize, usize),
+    ValueTooLarge,
+    IoError(io::Error),
 }
 
 impl Display for Day1Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
+        match self {
             Self::NoDigits => write!(f, "No digits on a line!"),
+            Self::TooFewDigits(needed, found) => write!(
+                f,
+                "Needed {} digits on a line, but only found {}",
+                needed, found
+            ),
+            Self::ValueTooLarge => write!(f, "Too many digits to fit in a number!"),
+            Self::InvalidWord(line, text) => write!(
+                f,
+                "Expected a word and the digit it means on line {}, like \"seven 7\", but got '{}'",
+                line, text
+            ),
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day1Error {}
+impl Error for Day1Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::NoDigits
+            | Self::InvalidWord(..)
+            | Self::TooFewDigits(..)
+            | Self::ValueTooLarge => None,
+        }
+    }
+}
 
-impl TryFrom<File> for State {
-    type Error = io::Error;
+impl PuzzleError for Day1Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::NoDigits | Self::TooFewDigits(..) => Category::InvalidPuzzle,
+            Self::ValueTooLarge => Category::Unsolvable,
+            Self::InvalidWord(..) => Category::Parse,
+            Self::IoError(_) => Category::Io,
+        }
+    }
+}
 
-    fn try_from(value: File) -> io::Result<Self> {
-        let buf = BufReader::new(value);
-        let lines = buf.lines().collect::<io::Result<Vec<String>>>()?;
+// `io::Error` can't be compared, so the tests settle for its kind.
+impl PartialEq for Day1Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NoDigits, Self::NoDigits) => true,
+            (Self::ValueTooLarge, Self::ValueTooLarge) => true,
+            (Self::TooFewDigits(left, left_found), Self::TooFewDigits(right, right_found)) => {
+                left == right && left_found == right_found
+            }
+            (Self::InvalidWord(left_line, left), Self::InvalidWord(right_line, right)) => {
+                left_line == right_line && left == right
+            }
+            (Self::IoError(left), Self::IoError(right)) => left.kind() == right.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for Day1Error {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl PuzzleInput for State {
+    type Error = Day1Error;
+
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let lines = input::records(reader)
+            .map(|(_, line)| line)
+            .collect::<io::Result<Vec<String>>>()?;
         Ok(State(lines))
     }
 }
 
-impl From<&str> for State {
-    fn from(value: &str) -> Self {
-        State(value.split('\n').map(String::from).collect())
+#[cfg(test)]
+mod tests {
+    use super::part1::Part1;
+    use super::part2::{Part2, EXAMPLE};
+    use super::State;
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
+
+    #[test]
+    fn never_panics_on_garbage() {
+        // Day 1 doesn't look inside a line until it's solving, so that's what needs fuzzing.
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = State::from_reader(input).map(Part1::solve);
+            let _ = State::from_reader(input).map(Part2::solve);
+        });
     }
 }
@@ -1,39 +1,57 @@
+use super::engine::Calibrator;
 use super::*;
+use crate::solution::{Example, Solution};
+use std::sync::OnceLock;
 
-pub fn sum_calibration_values(state: State) -> Result<u32, Day1Error> {
-    let results: Vec<u32> = state
-        .0
-        .iter()
-        .map(|str| parse_calibration_value(str))
-        .collect::<Result<Vec<u32>, Day1Error>>()?;
+const EXAMPLE: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
 
-    Ok(results.iter().sum())
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 1;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "142",
+    }];
+
+    type Input = State;
+    type Output = u64;
+    type Error = Day1Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_calibration_values(input)
+    }
+}
+
+pub fn sum_calibration_values(state: State) -> Result<u64, Day1Error> {
+    calibrator().sum(&state)
+}
+
+// Reads and sums one line at a time instead of collecting a `State` first, so a calibration
+// document can be as big as you like.
+pub fn sum_calibration_values_from<R: BufRead>(reader: R) -> Result<u64, Day1Error> {
+    calibrator().sum_lines(reader)
 }
 
-fn parse_calibration_value(line: &str) -> Result<u32, Day1Error> {
-    let tens = line
-        .chars()
-        .find(|char| char.is_ascii_digit())
-        .ok_or(Day1Error::NoDigits)?
-        .to_digit(10)
-        // Unwrapping here is safe because we already know it's a digit.
-        .unwrap()
-        * 10;
-    let ones = line
-        .chars()
-        .filter(|char| char.is_ascii_digit())
-        .next_back()
-        // Unwrapping here is safe because we know there's at least one digit from above.
-        .unwrap()
-        .to_digit(10)
-        .unwrap();
-
-    Ok(tens + ones)
+pub fn parse_calibration_value(line: &str) -> Result<u64, Day1Error> {
+    calibrator().value(line)
+}
+
+// Only the written digits count, first and last.
+pub(super) fn calibrator() -> &'static Calibrator {
+    static CALIBRATOR: OnceLock<Calibrator> = OnceLock::new();
+    CALIBRATOR.get_or_init(Calibrator::part1)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_calibration_value, sum_calibration_values, Day1Error};
+    use super::{
+        parse_calibration_value, sum_calibration_values, sum_calibration_values_from, Day1Error,
+        State, EXAMPLE,
+    };
+    use crate::input::PuzzleInput;
 
     #[test]
     fn can_parse_line() {
@@ -86,8 +104,17 @@ mod tests {
 
     #[test]
     fn provided_test() {
-        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
-        let result = sum_calibration_values(input.into());
+        let input = EXAMPLE;
+        let result = sum_calibration_values(State::from_reader(input.as_bytes()).unwrap());
         assert_eq!(result, Ok(142),);
     }
+
+    #[test]
+    fn streams_the_same_answer() {
+        assert_eq!(sum_calibration_values_from(EXAMPLE.as_bytes()), Ok(142));
+        assert_eq!(
+            sum_calibration_values_from("1abc2\n\nnodigits\n".as_bytes()),
+            Err(Day1Error::NoDigits)
+        );
+    }
 }
@@ -1,42 +1,85 @@
+use super::engine::Calibrator;
+use super::vocabulary::{DIGITS, ENGLISH};
 use super::*;
+use crate::solution::{Example, Solution};
+use std::sync::OnceLock;
 
-const PATTERNS: [&str; 20] = [
-    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three", "four",
-    "five", "six", "seven", "eight", "nine",
-];
+pub(super) const EXAMPLE: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
 
-pub fn sum_calibration_values(state: State) -> Result<usize, Day1Error> {
-    let results: Vec<usize> = state
-        .0
-        .iter()
-        .map(|str| parse_calibration_value(str))
-        .collect::<Result<Vec<usize>, Day1Error>>()?;
+pub struct Part2;
 
-    Ok(results.iter().sum())
+impl Solution for Part2 {
+    const DAY: usize = 1;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "281",
+    }];
+
+    type Input = State;
+    type Output = u64;
+    type Error = Day1Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_calibration_values(input)
+    }
 }
 
-fn parse_calibration_value(line: &str) -> Result<usize, Day1Error> {
-    let mut earliest_match: Option<(usize, usize)> = None;
-    let mut latest_match: Option<(usize, usize)> = None;
+pub fn sum_calibration_values(state: State) -> Result<u64, Day1Error> {
+    calibrator().sum(&state)
+}
 
-    for pattern in PATTERNS {
-        if let Some(index) = line.match_indices(pattern).next() {
+// Same as part 2, but with the digits spelled out in some other vocabulary.
+pub fn sum_calibration_values_with(
+    state: &State,
+    calibrator: &Calibrator,
+) -> Result<u64, Day1Error> {
+    calibrator.sum(state)
+}
+
+// Like part 1's, one line at a time without ever holding the whole document.
+pub fn sum_calibration_values_from<R: BufRead>(reader: R) -> Result<u64, Day1Error> {
+    calibrator().sum_lines(reader)
+}
+
+// Every digit, written or spelled out, found in a single pass over the line. "eightwo" starts
+// with eight and ends with two, since the words are allowed to share letters.
+pub fn parse_calibration_value(line: &str) -> Result<u64, Day1Error> {
+    calibrator().value(line)
+}
+
+// Digits and English words, first and last.
+pub(super) fn calibrator() -> &'static Calibrator {
+    static CALIBRATOR: OnceLock<Calibrator> = OnceLock::new();
+    CALIBRATOR.get_or_init(Calibrator::part2)
+}
+
+// The way this used to work, searching the whole line from both ends once per pattern. Kept
+// around to check the matcher against and to benchmark it with.
+pub fn parse_calibration_value_by_search(line: &str) -> Result<u64, Day1Error> {
+    let mut earliest_match: Option<(usize, u64)> = None;
+    let mut latest_match: Option<(usize, u64)> = None;
+
+    for &(pattern, value) in DIGITS.iter().chain(ENGLISH) {
+        let value = value as u64;
+        if let Some(index) = line.find(pattern) {
             if let Some(earliest) = earliest_match {
-                if index.0 < earliest.0 {
-                    earliest_match = Some((index.0, parse_num_str(index.1)));
+                if index < earliest.0 {
+                    earliest_match = Some((index, value));
                 }
             } else {
-                earliest_match = Some((index.0, parse_num_str(index.1)));
+                earliest_match = Some((index, value));
             }
         }
 
-        if let Some(index) = line.rmatch_indices(pattern).next() {
+        if let Some(index) = line.rfind(pattern) {
             if let Some(latest) = latest_match {
-                if index.0 > latest.0 {
-                    latest_match = Some((index.0, parse_num_str(index.1)));
+                if index > latest.0 {
+                    latest_match = Some((index, value));
                 }
             } else {
-                latest_match = Some((index.0, parse_num_str(index.1)));
+                latest_match = Some((index, value));
             }
         }
     }
@@ -48,25 +91,15 @@ fn parse_calibration_value(line: &str) -> Result<usize, Day1Error> {
     Ok(earliest_match.unwrap().1 * 10 + latest_match.unwrap().1)
 }
 
-fn parse_num_str(slice: &str) -> usize {
-    match slice {
-        "0" | "zero" => 0,
-        "1" | "one" => 1,
-        "2" | "two" => 2,
-        "3" | "three" => 3,
-        "4" | "four" => 4,
-        "5" | "five" => 5,
-        "6" | "six" => 6,
-        "7" | "seven" => 7,
-        "8" | "eight" => 8,
-        "9" | "nine" => 9,
-        misc => unreachable!("Somehow I parsed a {}", misc),
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{parse_calibration_value, sum_calibration_values, Day1Error};
+    use super::{
+        parse_calibration_value, parse_calibration_value_by_search, sum_calibration_values,
+        sum_calibration_values_from, Day1Error, State, EXAMPLE,
+    };
+    use crate::fuzz::Rng;
+    use crate::input::PuzzleInput;
+    use std::io::{self, BufReader, Read};
 
     #[test]
     fn can_parse_line() {
@@ -131,10 +164,73 @@ mod tests {
         assert_eq!(result, Err(Day1Error::NoDigits));
     }
 
+    #[test]
+    fn words_can_share_letters() {
+        assert_eq!(parse_calibration_value("eightwo"), Ok(82));
+        assert_eq!(parse_calibration_value("oneight"), Ok(18));
+        assert_eq!(parse_calibration_value("twone"), Ok(21));
+        assert_eq!(parse_calibration_value("sevenine"), Ok(79));
+    }
+
+    #[test]
+    fn agrees_with_searching_each_pattern() {
+        // Mostly letters from the digit words, so the lines are full of near misses.
+        let alphabet = b"onetwhrfuivsxgz0123456789";
+        let mut rng = Rng(2023);
+        for _ in 0..2000 {
+            let length = rng.below(30);
+            let line = (0..length)
+                .map(|_| alphabet[rng.below(alphabet.len())] as char)
+                .collect::<String>();
+            assert_eq!(
+                parse_calibration_value(&line),
+                parse_calibration_value_by_search(&line),
+                "Disagreed on {:?}",
+                line
+            );
+        }
+    }
+
+    // The same line over and over, made up as it's read, so it never exists all at once.
+    struct Repeat {
+        line: &'static [u8],
+        times: usize,
+        at: usize,
+    }
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.times == 0 {
+                return Ok(0);
+            }
+
+            let count = buf.len().min(self.line.len() - self.at);
+            buf[..count].copy_from_slice(&self.line[self.at..self.at + count]);
+            self.at += count;
+            if self.at == self.line.len() {
+                self.at = 0;
+                self.times -= 1;
+            }
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn streams_big_documents() {
+        assert_eq!(sum_calibration_values_from(EXAMPLE.as_bytes()), Ok(281));
+
+        let reader = BufReader::new(Repeat {
+            line: b"xtwone3four\r\n",
+            times: 100_000,
+            at: 0,
+        });
+        assert_eq!(sum_calibration_values_from(reader), Ok(2_400_000));
+    }
+
     #[test]
     fn provided_test() {
-        let input = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
-        let result = sum_calibration_values(input.into());
+        let input = EXAMPLE;
+        let result = sum_calibration_values(State::from_reader(input.as_bytes()).unwrap());
         assert_eq!(result, Ok(281),);
     }
 }
@@ -0,0 +1,141 @@
+use super::explain::{self, Explanation};
+use super::{Day1Error, State};
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 1;
+    const COMMANDS: &'static [Command] = &[
+        Command {
+            name: "explain",
+            usage: "explain [LINE]",
+            help: "Show which tokens every line's value came from in each part, or just LINE's",
+        },
+        Command {
+            name: "disagreements",
+            usage: "disagreements",
+            help: "Explain only the lines where part 1 and part 2 get different values",
+        },
+    ];
+
+    type Input = State;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" if args.is_empty() => Ok(input
+                .0
+                .iter()
+                .enumerate()
+                .map(|(index, line)| describe(index + 1, line))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            "explain" => {
+                let number = query::argument::<usize>(args, "explain [LINE]")?;
+                let line = number
+                    .checked_sub(1)
+                    .and_then(|index| input.0.get(index))
+                    .ok_or(QueryError::NotFound(format!("line {}", number)))?;
+                Ok(describe(number, line))
+            }
+            "disagreements" => {
+                if !args.is_empty() {
+                    return Err(QueryError::Usage("disagreements"));
+                }
+
+                let disagreements = input
+                    .0
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| explain::compare(line).disagrees())
+                    .map(|(index, line)| describe(index + 1, line))
+                    .collect::<Vec<_>>();
+                if disagreements.is_empty() {
+                    return Ok(String::from("Part 1 and part 2 agree on every line"));
+                }
+
+                Ok(format!(
+                    "{}\n{} of {} lines disagree",
+                    disagreements.join("\n"),
+                    disagreements.len(),
+                    input.0.len()
+                ))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+fn describe(number: usize, line: &str) -> String {
+    let comparison = explain::compare(line);
+    let describe = |explanation: Result<Explanation, Day1Error>| match explanation {
+        Ok(explanation) => explanation.to_string(),
+        Err(err) => err.to_string(),
+    };
+    format!(
+        "Line {}: {}\n  part 1: {}\n  part 2: {}",
+        number,
+        line,
+        describe(comparison.part1),
+        describe(comparison.part2)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Queries, State};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query, QueryError};
+
+    #[test]
+    fn explains_a_line() {
+        let input = State::from_reader("two1nine\nabc".as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["1"]).unwrap(),
+            "Line 1: two1nine\n  part 1: 11 from '1' (digit, bytes 3..4) and '1' (digit, bytes 3..4)\n  part 2: 29 from 'two' (word, bytes 0..3) and 'nine' (word, bytes 4..8)"
+        );
+        assert_eq!(
+            Queries::query(&input, "explain", &["2"]).unwrap(),
+            "Line 2: abc\n  part 1: No digits on a line!\n  part 2: No digits on a line!"
+        );
+        assert_eq!(
+            Queries::query(&input, "explain", &["3"]),
+            Err(QueryError::NotFound(String::from("line 3")))
+        );
+        assert_eq!(
+            Queries::query(&input, "explain", &[])
+                .unwrap()
+                .lines()
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn shows_disagreements() {
+        let input = State::from_reader("1abc2\neightwo3\nabc\n".as_bytes()).unwrap();
+        let disagreements = Queries::query(&input, "disagreements", &[]).unwrap();
+        assert!(disagreements.starts_with("Line 2: eightwo3\n  part 1: 33 from '3'"));
+        assert!(disagreements.ends_with("\n1 of 3 lines disagree"));
+
+        let input = State::from_reader("1abc2\n".as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "disagreements", &[]).unwrap(),
+            "Part 1 and part 2 agree on every line"
+        );
+        assert_eq!(
+            Queries::query(&input, "disagreements", &["2"]),
+            Err(QueryError::Usage("disagreements"))
+        );
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics("two1nine\neightwothree\nabcone2threexyz\n", |input| {
+            if let Ok(input) = State::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
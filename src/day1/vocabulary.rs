@@ -0,0 +1,245 @@
+use super::engine::{Calibrator, Rule};
+use super::Day1Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+// Written digits count in every language.
+pub const DIGITS: &[(&str, usize)] = &[
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const ENGLISH: &[(&str, usize)] = &[
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub const GERMAN: &[(&str, usize)] = &[
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+pub const SPANISH: &[(&str, usize)] = &[
+    ("cero", 0),
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+pub const FRENCH: &[(&str, usize)] = &[
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+// The Romans didn't have a zero. These overlap ("viii" has "vii", "ii" and "i" in it), which is
+// why the longest match wins at either end of a line.
+pub const ROMAN: &[(&str, usize)] = &[
+    ("i", 1),
+    ("ii", 2),
+    ("iii", 3),
+    ("iv", 4),
+    ("v", 5),
+    ("vi", 6),
+    ("vii", 7),
+    ("viii", 8),
+    ("ix", 9),
+];
+
+pub const BUILT_IN: &[(&str, &[(&str, usize)])] = &[
+    ("english", ENGLISH),
+    ("german", GERMAN),
+    ("spanish", SPANISH),
+    ("french", FRENCH),
+    ("roman", ROMAN),
+];
+
+// Which words count as digits when reading a calibration document, on top of the digits
+// themselves. Part 2 uses English, but the elves' documents aren't always in English.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vocabulary {
+    words: Vec<(String, usize)>,
+    ignore_case: bool,
+}
+
+impl Vocabulary {
+    pub fn new(words: &[(&str, usize)]) -> Self {
+        Vocabulary {
+            words: words
+                .iter()
+                .map(|(word, value)| (String::from(*word), *value))
+                .collect(),
+            ignore_case: false,
+        }
+    }
+
+    // No words at all, just the digits, like part 1.
+    pub fn digits() -> Self {
+        Self::new(&[])
+    }
+
+    pub fn english() -> Self {
+        Self::new(ENGLISH)
+    }
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        BUILT_IN
+            .iter()
+            .find(|(built_in, _)| built_in.eq_ignore_ascii_case(name))
+            .map(|(_, words)| Self::new(words))
+    }
+
+    // A built-in vocabulary by name, or else a word list from a file.
+    pub fn load(name_or_path: &str) -> Result<Self, Day1Error> {
+        match Self::built_in(name_or_path) {
+            Some(vocabulary) => Ok(vocabulary),
+            None => Self::from_reader(BufReader::new(File::open(name_or_path)?)),
+        }
+    }
+
+    // One word and the digit it means per line, like "sieben 7". Blank lines and anything after
+    // a `#` are skipped.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, Day1Error> {
+        let mut words = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.split_once('#').map_or(&line[..], |(line, _)| line);
+            let mut parts = line.split_whitespace();
+            let (word, value) = match (parts.next(), parts.next(), parts.next()) {
+                (None, _, _) => continue,
+                (Some(word), Some(value), None) => (word, value),
+                _ => return Err(Day1Error::InvalidWord(index + 1, String::from(line.trim()))),
+            };
+
+            match value.parse::<usize>() {
+                Ok(value) if value <= 9 => words.push((String::from(word), value)),
+                _ => return Err(Day1Error::InvalidWord(index + 1, String::from(line.trim()))),
+            }
+        }
+
+        Ok(Vocabulary {
+            words,
+            ignore_case: false,
+        })
+    }
+
+    // "Sieben", "SIEBEN" and "sieben" all count.
+    pub fn ignoring_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    pub fn words(&self) -> &[(String, usize)] {
+        &self.words
+    }
+
+    pub fn ignores_case(&self) -> bool {
+        self.ignore_case
+    }
+
+    // Reads lines the way part 2 does, but with these words.
+    pub fn calibrator(&self) -> Calibrator {
+        Calibrator::new(self, Rule::FirstAndLast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day1Error, Vocabulary};
+
+    fn value(vocabulary: &Vocabulary, line: &str) -> Result<u64, Day1Error> {
+        vocabulary.calibrator().value(line)
+    }
+
+    #[test]
+    fn reads_other_languages() {
+        let german = Vocabulary::built_in("german").unwrap();
+        assert_eq!(value(&german, "xfünfzweiachtz"), Ok(58));
+        assert_eq!(value(&german, "siebeneins"), Ok(71));
+        assert_eq!(value(&german, "three"), Err(Day1Error::NoDigits));
+
+        let spanish = Vocabulary::built_in("Spanish").unwrap();
+        assert_eq!(value(&spanish, "unodos3cuatroz"), Ok(14));
+
+        let french = Vocabulary::built_in("french").unwrap();
+        assert_eq!(value(&french, "zérodeuxhuitneuf"), Ok(9));
+        assert!(Vocabulary::built_in("klingon").is_none());
+    }
+
+    #[test]
+    fn reads_roman_numerals() {
+        let roman = Vocabulary::built_in("roman").unwrap();
+        assert_eq!(value(&roman, "viii"), Ok(88));
+        assert_eq!(value(&roman, "xivx2"), Ok(42));
+        assert_eq!(value(&roman, "ix and vi"), Ok(96));
+    }
+
+    #[test]
+    fn can_ignore_case() {
+        let english = Vocabulary::english();
+        assert_eq!(value(&english, "One2THREE"), Ok(22));
+        assert_eq!(value(&english.ignoring_case(), "One2THREE"), Ok(13));
+
+        let german = Vocabulary::built_in("german").unwrap().ignoring_case();
+        assert_eq!(value(&german, "FÜNF und Vier"), Ok(54));
+    }
+
+    #[test]
+    fn loads_word_lists() {
+        let file = "# Dutch, partly\neen 1\n\ntwee 2   # two\ndrie 3\n";
+        let dutch = Vocabulary::from_reader(file.as_bytes()).unwrap();
+        assert_eq!(dutch.words().len(), 3);
+        assert_eq!(value(&dutch, "tweeendrie"), Ok(23));
+
+        assert_eq!(
+            Vocabulary::from_reader("een 1\ntwee\n".as_bytes()),
+            Err(Day1Error::InvalidWord(2, String::from("twee")))
+        );
+        assert_eq!(
+            Vocabulary::from_reader("tien 10\n".as_bytes()),
+            Err(Day1Error::InvalidWord(1, String::from("tien 10")))
+        );
+        assert!(Vocabulary::load("german").is_ok());
+        assert!(matches!(
+            Vocabulary::load("no/such/vocabulary.txt"),
+            Err(Day1Error::IoError(_))
+        ));
+    }
+}
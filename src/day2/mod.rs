@@ -1,44 +1,58 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 #[cfg(test)]
 mod tests;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead};
+
+pub const TITLE: &str = "Cube Conundrum";
+
+pub const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
 #[derive(Debug)]
 pub struct PuzzleState(Vec<Game>);
 
 #[derive(Debug, PartialEq)]
-struct Game {
+pub struct Game {
     id: usize,
     pulls: Vec<Pull>,
 }
 
-impl TryFrom<File> for PuzzleState {
-    type Error = Day2Error;
+impl PuzzleState {
+    pub fn games(&self) -> &[Game] {
+        &self.0
+    }
+}
 
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let buf = BufReader::new(value);
-        let games = buf
-            .lines()
-            .map(|line| line.map_err(Day2Error::IoError)?.try_into())
-            .collect::<Result<Vec<Game>, Day2Error>>()?;
+impl Game {
+    pub fn id(&self) -> usize {
+        self.id
+    }
 
-        Ok(PuzzleState(games))
+    pub fn pulls(&self) -> &[Pull] {
+        &self.pulls
     }
 }
 
-impl TryFrom<&str> for PuzzleState {
-    type Error = Day2Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let games = value
-            .lines()
-            .map(|line| String::from(line).try_into())
-            .collect::<Result<Vec<Game>, Day2Error>>()?;
+impl PuzzleInput for PuzzleState {
+    type Error = Located<Day2Error>;
+
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let games = input::records(reader)
+            .map(|(index, line)| {
+                let line = line.map_err(Day2Error::IoError)?;
+                Game::try_from(line.clone()).map_err(|err| {
+                    let location = Location::new(index, &line, err.token());
+                    Located::new(err, location)
+                })
+            })
+            .collect::<Result<Vec<Game>, _>>()?;
 
         Ok(PuzzleState(games))
     }
@@ -51,14 +65,16 @@ impl TryFrom<String> for Game {
         let mut parts = value.split(": ");
         let game_id = parts.next().ok_or(Day2Error::NoGameID(value.clone()))?;
 
-        let id: usize = game_id[5..]
+        let id: usize = game_id
+            .strip_prefix("Game ")
+            .ok_or(Day2Error::NoGameID(value.clone()))?
             .parse()
             .map_err(|_| Day2Error::NoGameID(value.clone()))?;
 
         let pulls = parts.next().ok_or(Day2Error::NoPulls(value.clone()))?;
         let pulls = pulls
             .split("; ")
-            .map(|pull| pull.try_into())
+            .map(|pull| Pull::parse(&value, pull))
             .collect::<Result<Vec<Pull>, Day2Error>>()?;
 
         Ok(Game { id, pulls })
@@ -66,31 +82,55 @@ impl TryFrom<String> for Game {
 }
 
 #[derive(Debug, PartialEq)]
-struct Pull {
+pub struct Pull {
     red: usize,
     green: usize,
     blue: usize,
 }
 
-// This is parsing the "3 blue, 4 red" part.
+impl Pull {
+    pub fn red(&self) -> usize {
+        self.red
+    }
+
+    pub fn green(&self) -> usize {
+        self.green
+    }
+
+    pub fn blue(&self) -> usize {
+        self.blue
+    }
+}
+
+// This is parsing the "3 blue, 4 red" part on its own.
 impl TryFrom<&str> for Pull {
     type Error = Day2Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, value)
+    }
+}
+
+impl Pull {
+    // `pull` is part of `line`, which is what the errors point into.
+    fn parse(line: &str, pull: &str) -> Result<Self, Day2Error> {
         let mut red: usize = 0;
         let mut green: usize = 0;
         let mut blue: usize = 0;
 
-        value
-            .split(", ")
+        pull.split(", ")
             .map(|cube| -> Result<(), Day2Error> {
                 let mut parts = cube.split(' ');
-                let count = parts.next().ok_or(Day2Error::NoCount(String::from(cube)))?;
-                let color = parts.next().ok_or(Day2Error::NoColor(String::from(cube)))?;
+                let count = parts
+                    .next()
+                    .ok_or_else(|| Day2Error::NoCount(Token::new(line, cube)))?;
+                let color = parts
+                    .next()
+                    .ok_or_else(|| Day2Error::NoColor(Token::new(line, cube)))?;
 
                 let count: usize = count
                     .parse()
-                    .map_err(|_| Day2Error::NotANumber(String::from(cube)))?;
+                    .map_err(|_| Day2Error::NotANumber(Token::new(line, cube)))?;
                 if color.contains("red") {
                     red = count;
                 } else if color.contains("green") {
@@ -98,7 +138,7 @@ impl TryFrom<&str> for Pull {
                 } else if color.contains("blue") {
                     blue = count;
                 } else {
-                    return Err(Day2Error::UnrecognizedColor(String::from(cube)));
+                    return Err(Day2Error::UnrecognizedColor(Token::new(line, cube)));
                 }
 
                 Ok(())
@@ -111,17 +151,32 @@ impl TryFrom<&str> for Pull {
 
 #[derive(Debug)]
 pub enum Day2Error {
-    NoCount(String),
-    NoColor(String),
-    NotANumber(String),
-    UnrecognizedColor(String),
+    NoCount(Token),
+    NoColor(Token),
+    NotANumber(Token),
+    UnrecognizedColor(Token),
 
     NoGameID(String),
     NoPulls(String),
 
+    TooLarge,
+
     IoError(io::Error),
 }
 
+impl Day2Error {
+    // The bit of the line this error is about, so it can be pointed at.
+    fn token(&self) -> Option<&Token> {
+        match self {
+            Self::NoCount(cube)
+            | Self::NoColor(cube)
+            | Self::NotANumber(cube)
+            | Self::UnrecognizedColor(cube) => Some(cube),
+            Self::NoGameID(_) | Self::NoPulls(_) | Self::TooLarge | Self::IoError(_) => None,
+        }
+    }
+}
+
 impl Display for Day2Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -133,9 +188,28 @@ impl Display for Day2Error {
             Self::NoGameID(line) => write!(f, "No game ID on line {}.", line),
             Self::NoPulls(line) => write!(f, "No pulls on line {}.", line),
 
-            Self::IoError(err) => write!(f, "{}", err),
+            Self::TooLarge => write!(f, "Answer too large to fit in a number."),
+
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day2Error {}
+impl Error for Day2Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl PuzzleError for Day2Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
+    }
+}
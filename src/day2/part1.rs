@@ -1,7 +1,28 @@
-use super::{Day2Error, PuzzleState};
+use super::{Day2Error, PuzzleState, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 2;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "8",
+    }];
+
+    type Input = PuzzleState;
+    type Output = usize;
+    type Error = Day2Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_impossible_game_ids(input)
+    }
+}
 
 pub fn sum_impossible_game_ids(state: PuzzleState) -> Result<usize, Day2Error> {
-    Ok(state
+    state
         .0
         .iter()
         .filter(|game| {
@@ -9,5 +30,6 @@ pub fn sum_impossible_game_ids(state: PuzzleState) -> Result<usize, Day2Error> {
                 .iter()
                 .all(|pull| pull.red <= 12 && pull.green <= 13 && pull.blue <= 14)
         })
-        .fold(0, |sum, game| sum + game.id))
+        .try_fold(0_usize, |sum, game| sum.checked_add(game.id))
+        .ok_or(Day2Error::TooLarge)
 }
@@ -1,23 +1,54 @@
-use super::{Day2Error, Pull, PuzzleState};
+use super::{Day2Error, Game, Pull, PuzzleState, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 2;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "2286",
+    }];
+
+    type Input = PuzzleState;
+    type Output = usize;
+    type Error = Day2Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_cube_powers(input)
+    }
+}
 
 pub fn sum_cube_powers(state: PuzzleState) -> Result<usize, Day2Error> {
-    Ok(state
+    state
         .0
         .iter()
-        .map(|game| {
-            let min_pull = game.pulls.iter().fold(
-                Pull {
-                    red: 0,
-                    green: 0,
-                    blue: 0,
-                },
-                |min, pull| Pull {
-                    red: pull.red.max(min.red),
-                    green: pull.green.max(min.green),
-                    blue: pull.blue.max(min.blue),
-                },
-            );
-            min_pull.red * min_pull.green * min_pull.blue
-        })
-        .sum())
+        .map(|game| power(&min_pull(game)))
+        .try_fold(0_usize, |sum, power| sum.checked_add(power?))
+        .ok_or(Day2Error::TooLarge)
+}
+
+// The fewest cubes of each colour that could have made every pull in the game.
+pub(super) fn min_pull(game: &Game) -> Pull {
+    game.pulls.iter().fold(
+        Pull {
+            red: 0,
+            green: 0,
+            blue: 0,
+        },
+        |min, pull| Pull {
+            red: pull.red.max(min.red),
+            green: pull.green.max(min.green),
+            blue: pull.blue.max(min.blue),
+        },
+    )
+}
+
+// None if the power doesn't fit in a usize.
+pub(super) fn power(pull: &Pull) -> Option<usize> {
+    pull.red
+        .checked_mul(pull.green)
+        .and_then(|power| power.checked_mul(pull.blue))
 }
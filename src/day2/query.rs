@@ -0,0 +1,82 @@
+use super::{part2, PuzzleState};
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 2;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "explain",
+        usage: "explain GAME",
+        help: "Show the fewest cubes a game needs and whether part 1 allows it",
+    }];
+
+    type Input = PuzzleState;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let id = query::argument::<usize>(args, "explain GAME")?;
+                let game = input
+                    .0
+                    .iter()
+                    .find(|game| game.id == id)
+                    .ok_or(QueryError::NotFound(format!("game {}", id)))?;
+
+                let fewest = part2::min_pull(game);
+                let power = match part2::power(&fewest) {
+                    Some(power) => power.to_string(),
+                    None => String::from("too large to count"),
+                };
+                let possible = fewest.red <= 12 && fewest.green <= 13 && fewest.blue <= 14;
+
+                Ok(format!(
+                    "Game {}: needs {} red, {} green, {} blue\n{} with 12 red, 13 green, 14 blue\npower {}",
+                    game.id,
+                    fewest.red,
+                    fewest.green,
+                    fewest.blue,
+                    if possible { "possible" } else { "impossible" },
+                    power
+                ))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{PuzzleState, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_game() {
+        let input = PuzzleState::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["3"]).unwrap(),
+            "Game 3: needs 20 red, 13 green, 6 blue\nimpossible with 12 red, 13 green, 14 blue\npower 1560"
+        );
+    }
+
+    #[test]
+    fn doesnt_overflow_on_huge_games() {
+        let input = "Game 1: 4294967296 red, 4294967296 green, 2 blue\n";
+        let input = PuzzleState::from_reader(input.as_bytes()).unwrap();
+        assert!(Queries::query(&input, "explain", &["1"])
+            .unwrap()
+            .ends_with("power too large to count"));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = PuzzleState::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
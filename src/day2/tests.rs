@@ -1,6 +1,8 @@
 use super::part1::sum_impossible_game_ids;
 use super::part2::sum_cube_powers;
-use super::{Day2Error, Game, Pull, PuzzleState};
+use super::{Day2Error, Game, Pull, PuzzleState, EXAMPLE};
+use crate::fuzz;
+use crate::input::PuzzleInput;
 
 #[test]
 fn parses_colors() {
@@ -34,7 +36,7 @@ fn errors_if_empty_string() {
     let result: Result<Pull, Day2Error> = cube.try_into();
     match result {
         Ok(pull) => panic!("Got success {:?}", pull),
-        Err(Day2Error::NoColor(err_cube)) => assert_eq!(err_cube, cube),
+        Err(Day2Error::NoColor(err_cube)) => assert_eq!(err_cube.text, cube),
         Err(err) => panic!("Got wrong error {}", err),
     }
 }
@@ -45,7 +47,7 @@ fn errors_if_no_color() {
     let result: Result<Pull, Day2Error> = cube.try_into();
     match result {
         Ok(pull) => panic!("Got success {:?}", pull),
-        Err(Day2Error::NoColor(err_cube)) => assert_eq!(err_cube, cube),
+        Err(Day2Error::NoColor(err_cube)) => assert_eq!(err_cube.text, cube),
         Err(err) => panic!("Got wrong error {}", err),
     }
 }
@@ -56,7 +58,7 @@ fn errors_if_count_isnt_number() {
     let result: Result<Pull, Day2Error> = cube.try_into();
     match result {
         Ok(pull) => panic!("Got success {:?}", pull),
-        Err(Day2Error::NotANumber(err_cube)) => assert_eq!(err_cube, cube),
+        Err(Day2Error::NotANumber(err_cube)) => assert_eq!(err_cube.text, cube),
         Err(err) => panic!("Got wrong error {}", err),
     }
 }
@@ -67,7 +69,7 @@ fn errors_if_color_is_bad() {
     let result: Result<Pull, Day2Error> = cube.try_into();
     match result {
         Ok(pull) => panic!("Got success {:?}", pull),
-        Err(Day2Error::UnrecognizedColor(err_cube)) => assert_eq!(err_cube, cube),
+        Err(Day2Error::UnrecognizedColor(err_cube)) => assert_eq!(err_cube.text, cube),
         Err(err) => panic!("Got wrong error {}", err),
     }
 }
@@ -105,7 +107,7 @@ fn parses_a_game() {
 
 #[test]
 fn provided_example_part1() {
-    let state: PuzzleState = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".try_into().unwrap();
+    let state = PuzzleState::from_reader(EXAMPLE.as_bytes()).unwrap();
     println!("{:#?}", state);
     let result = sum_impossible_game_ids(state);
     match result {
@@ -116,7 +118,7 @@ fn provided_example_part1() {
 
 #[test]
 fn provided_example_part2() {
-    let state: PuzzleState = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".try_into().unwrap();
+    let state = PuzzleState::from_reader(EXAMPLE.as_bytes()).unwrap();
     println!("{:#?}", state);
     let result = sum_cube_powers(state);
     match result {
@@ -124,3 +126,19 @@ fn provided_example_part2() {
         Err(err) => panic!("Got error {}", err),
     }
 }
+
+#[test]
+fn points_at_bad_cube() {
+    let err =
+        PuzzleState::from_reader("Game 1: 3 blue\nGame 2: 1 red, 4 purple".as_bytes()).unwrap_err();
+    let location = err.location.unwrap();
+    assert_eq!((location.line, location.column, location.width), (2, 16, 8));
+}
+
+#[test]
+fn never_panics_on_garbage() {
+    fuzz::never_panics(EXAMPLE, |input| {
+        let _ = PuzzleState::from_reader(input).map(sum_impossible_game_ids);
+        let _ = PuzzleState::from_reader(input).map(sum_cube_powers);
+    });
+}
@@ -1,8 +1,16 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, BufRead};
+
+pub const TITLE: &str = "Gear Ratios";
+
+pub const EXAMPLE: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
 
 #[derive(Debug)]
 pub struct Puzzle {
@@ -10,37 +18,81 @@ pub struct Puzzle {
     symbols: Vec<Symbol>,
 }
 
+// A number in the schematic. `start` is the (x, y) of its first digit.
 #[derive(Debug)]
-struct Number {
+pub struct Number {
     start: (usize, usize),
     width: usize,
     value: usize,
 }
 
 #[derive(Debug)]
-struct Symbol {
+pub struct Symbol {
     position: (usize, usize),
     symbol: char,
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = io::Error;
+impl Puzzle {
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+}
+
+impl Number {
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+}
+
+impl Symbol {
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+
+    pub fn symbol(&self) -> char {
+        self.symbol
+    }
+}
+
+#[derive(Debug)]
+pub enum Day3Error {
+    NumberTooLarge(usize),
+    AnswerTooLarge,
+    IoError(io::Error),
+}
+
+impl PuzzleInput for Puzzle {
+    type Error = Day3Error;
 
-    fn try_from(value: File) -> Result<Self, Self::Error> {
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
         let mut numbers: Vec<Number> = vec![];
         let mut symbols: Vec<Symbol> = vec![];
 
-        let buf = BufReader::new(value);
-
-        for (y, line) in buf.lines().enumerate() {
+        for (y, line) in input::records(reader) {
             let line = line?;
+            let too_large = || Day3Error::NumberTooLarge(y + 1);
             let mut accumulated_number: usize = 0;
             let mut start_x: usize = 0;
             for (x, character) in line.chars().enumerate() {
                 match character {
                     '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
-                        accumulated_number *= 10;
-                        accumulated_number += character.to_digit(10).unwrap() as usize;
+                        let digit = character.to_digit(10).unwrap() as usize;
+                        accumulated_number = accumulated_number
+                            .checked_mul(10)
+                            .and_then(|number| number.checked_add(digit))
+                            .ok_or_else(too_large)?;
                     }
                     non_num => {
                         if accumulated_number > 0 {
@@ -77,51 +129,37 @@ impl TryFrom<File> for Puzzle {
     }
 }
 
-impl From<&str> for Puzzle {
-    fn from(value: &str) -> Self {
-        let mut numbers: Vec<Number> = vec![];
-        let mut symbols: Vec<Symbol> = vec![];
-
-        for (y, line) in value.lines().enumerate() {
-            let mut accumulated_number: usize = 0;
-            let mut start_x: usize = 0;
-            for (x, character) in line.chars().enumerate() {
-                match character {
-                    '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
-                        accumulated_number *= 10;
-                        accumulated_number += character.to_digit(10).unwrap() as usize;
-                    }
-                    non_num => {
-                        if accumulated_number > 0 {
-                            numbers.push(Number {
-                                start: (start_x, y),
-                                width: x - start_x,
-                                value: accumulated_number,
-                            });
-                            accumulated_number = 0;
-                        }
-                        start_x = x + 1;
+impl Display for Day3Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NumberTooLarge(line) => write!(f, "Number too large on line {}", line),
+            Self::AnswerTooLarge => write!(f, "Answer too large to fit in a number"),
+            Self::IoError(_) => write!(f, "I/O error"),
+        }
+    }
+}
 
-                        match non_num {
-                            '.' => (),
-                            symbol => symbols.push(Symbol {
-                                position: (x, y),
-                                symbol,
-                            }),
-                        }
-                    }
-                }
-            }
+impl Error for Day3Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::NumberTooLarge(_) | Self::AnswerTooLarge => None,
+        }
+    }
+}
 
-            if accumulated_number > 0 {
-                numbers.push(Number {
-                    start: (start_x, y),
-                    width: line.len() - start_x,
-                    value: accumulated_number,
-                });
-            }
+impl PuzzleError for Day3Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::NumberTooLarge(_) => Category::Parse,
+            Self::AnswerTooLarge => Category::Unsolvable,
         }
+    }
+}
 
-        Puzzle { numbers, symbols }
+impl From<io::Error> for Day3Error {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
     }
 }
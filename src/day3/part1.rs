@@ -1,7 +1,27 @@
-use super::Puzzle;
-use std::io;
+use super::{Day3Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
 
-pub fn sum_part_numbers(puzzle: Puzzle) -> Result<usize, io::Error> {
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 3;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "4361",
+    }];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day3Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_part_numbers(input)
+    }
+}
+
+pub fn sum_part_numbers(puzzle: Puzzle) -> Result<usize, Day3Error> {
     let part_numbers = puzzle
         .numbers
         .into_iter()
@@ -15,19 +35,36 @@ pub fn sum_part_numbers(puzzle: Puzzle) -> Result<usize, io::Error> {
         })
         .collect::<Vec<_>>();
 
-    Ok(part_numbers
+    part_numbers
         .into_iter()
-        .fold(0, |sum, number| sum + number.value))
+        .try_fold(0_usize, |sum, number| sum.checked_add(number.value))
+        .ok_or(Day3Error::AnswerTooLarge)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::Puzzle, sum_part_numbers};
+    use super::super::part2::sum_gear_ratios;
+    use super::{super::Puzzle, sum_part_numbers, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle_input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
-        let puzzle: Puzzle = puzzle_input.into();
+        let puzzle_input = EXAMPLE;
+        let puzzle = Puzzle::from_reader(puzzle_input.as_bytes()).unwrap();
         assert_eq!(sum_part_numbers(puzzle).unwrap_or(0), 4361);
     }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(sum_part_numbers);
+            let _ = Puzzle::from_reader(input).map(sum_gear_ratios);
+        });
+    }
+
+    #[test]
+    fn rejects_huge_numbers() {
+        assert!(Puzzle::from_reader("123456789012345678901234567890*".as_bytes()).is_err());
+    }
 }
@@ -1,9 +1,29 @@
-use super::Puzzle;
+use super::{Day3Error, Puzzle, EXAMPLE, TITLE};
 
-use std::io;
+use crate::solution::{Example, Solution};
 
-pub fn sum_gear_ratios(puzzle: Puzzle) -> Result<usize, io::Error> {
-    Ok(puzzle
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 3;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "467835",
+    }];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day3Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_gear_ratios(input)
+    }
+}
+
+pub fn sum_gear_ratios(puzzle: Puzzle) -> Result<usize, Day3Error> {
+    puzzle
         .symbols
         .into_iter()
         .filter_map(|symbol| {
@@ -28,22 +48,24 @@ pub fn sum_gear_ratios(puzzle: Puzzle) -> Result<usize, io::Error> {
                 .collect::<Vec<usize>>();
 
             if adjacent_numbers.len() == 2 {
-                Some(adjacent_numbers.into_iter().product::<usize>())
+                Some(adjacent_numbers[0].checked_mul(adjacent_numbers[1]))
             } else {
                 None
             }
         })
-        .sum())
+        .try_fold(0_usize, |sum, ratio| sum.checked_add(ratio?))
+        .ok_or(Day3Error::AnswerTooLarge)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::Puzzle, sum_gear_ratios};
+    use super::{super::Puzzle, sum_gear_ratios, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle_input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
-        let puzzle: Puzzle = puzzle_input.into();
+        let puzzle_input = EXAMPLE;
+        let puzzle = Puzzle::from_reader(puzzle_input.as_bytes()).unwrap();
         assert_eq!(sum_gear_ratios(puzzle).unwrap_or(0), 467835);
     }
 }
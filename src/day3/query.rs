@@ -0,0 +1,79 @@
+use super::Puzzle;
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 3;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "explain",
+        usage: "explain LINE",
+        help: "List the numbers on a line and the symbols next to each",
+    }];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let line = query::argument::<usize>(args, "explain LINE")?;
+                let numbers = input
+                    .numbers
+                    .iter()
+                    .filter(|number| line > 0 && number.start.1 == line - 1)
+                    .map(|number| {
+                        let (x, y) = number.start;
+                        let symbols = input
+                            .symbols
+                            .iter()
+                            .filter(|symbol| {
+                                let (sx, sy) = symbol.position;
+                                sx + 1 >= x && sx <= x + number.width && sy + 1 >= y && sy <= y + 1
+                            })
+                            .map(|symbol| symbol.symbol.to_string())
+                            .collect::<Vec<_>>();
+                        if symbols.is_empty() {
+                            format!("{}: no symbols, not a part", number.value)
+                        } else {
+                            format!("{}: next to {}", number.value, symbols.join(" "))
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if numbers.is_empty() {
+                    Err(QueryError::NotFound(format!("number on line {}", line)))
+                } else {
+                    Ok(numbers.join("\n"))
+                }
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_line() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["1"]).unwrap(),
+            "467: next to *\n114: no symbols, not a part"
+        );
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
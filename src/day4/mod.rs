@@ -1,55 +1,99 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error as IoError};
+use std::io::{BufRead, Error as IoError};
 use std::num::ParseIntError;
 
+pub const TITLE: &str = "Scratchcards";
+
+pub const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11\n";
+
 #[derive(Debug)]
 pub struct Puzzle(Vec<Card>);
 
 #[derive(Debug, PartialEq)]
-struct Card {
+pub struct Card {
     id: usize,
     winning_numbers: HashSet<usize>,
     scratched_numbers: HashSet<usize>,
 }
 
+impl Puzzle {
+    pub fn cards(&self) -> &[Card] {
+        &self.0
+    }
+}
+
+impl Card {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn winning_numbers(&self) -> &HashSet<usize> {
+        &self.winning_numbers
+    }
+
+    pub fn scratched_numbers(&self) -> &HashSet<usize> {
+        &self.scratched_numbers
+    }
+
+    // How many of the scratched numbers are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning_numbers
+            .intersection(&self.scratched_numbers)
+            .count()
+    }
+}
+
 #[derive(Debug)]
 pub enum Day4Error {
     NoColonSeparator(String),
+    NoCardID(String),
     NoPipe(String),
-    ParseIntError(ParseIntError),
+    ParseIntError(Token, ParseIntError),
+    // The card that should've been next, and the one that was.
+    OutOfOrder(usize, usize),
+    PastTheEnd(usize),
+    TooLarge,
     IoError(IoError),
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = Day4Error;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day4Error>;
 
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let value = BufReader::new(value);
-        Ok(Puzzle(
-            value
-                .lines()
-                .map(|line| (&line?[..]).try_into())
-                .collect::<Result<Vec<_>, _>>()?,
-        ))
-    }
-}
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let mut cards = Vec::<Card>::new();
+        for (index, line) in input::records(reader) {
+            let line = line.map_err(Day4Error::from)?;
+            let card = Card::try_from(&line[..]).map_err(|err| {
+                let location = Location::new(index, &line, err.token());
+                Located::new(err, location)
+            })?;
+            // Part 2 finds the cards a card wins by where they are, so the IDs have to line up.
+            if card.id != cards.len() + 1 {
+                return Err(Located::new(
+                    Day4Error::OutOfOrder(cards.len() + 1, card.id),
+                    Location::new(index, &line, None),
+                ));
+            }
+            cards.push(card);
+        }
 
-impl TryFrom<&str> for Puzzle {
-    type Error = Day4Error;
+        // "Cards will never make you copy a card past the end of the table."
+        for (index, card) in cards.iter().enumerate() {
+            if index + card.matches() >= cards.len() {
+                return Err(Day4Error::PastTheEnd(card.id).into());
+            }
+        }
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(Puzzle(
-            value
-                .lines()
-                .map(|line| line.try_into())
-                .collect::<Result<Vec<_>, _>>()?,
-        ))
+        Ok(Puzzle(cards))
     }
 }
 
@@ -60,7 +104,10 @@ impl TryFrom<&str> for Card {
         let (card_id, numbers) = value
             .split_once(": ")
             .ok_or(Day4Error::NoColonSeparator(String::from(value)))?;
-        let card_id: usize = card_id[5..].trim().parse()?;
+        let card_id = card_id
+            .strip_prefix("Card")
+            .ok_or(Day4Error::NoCardID(String::from(value)))?;
+        let card_id: usize = parse_number(value, card_id.trim())?;
 
         let (winning_numbers, scratched_numbers) = numbers
             .split_once(" | ")
@@ -71,7 +118,7 @@ impl TryFrom<&str> for Card {
                 if number.is_empty() {
                     None
                 } else {
-                    Some(number.parse::<usize>())
+                    Some(parse_number(value, number))
                 }
             })
             .collect::<Result<HashSet<_>, _>>()?;
@@ -81,7 +128,7 @@ impl TryFrom<&str> for Card {
                 if number.is_empty() {
                     None
                 } else {
-                    Some(number.parse::<usize>())
+                    Some(parse_number(value, number))
                 }
             })
             .collect::<Result<HashSet<_>, _>>()?;
@@ -94,22 +141,57 @@ impl TryFrom<&str> for Card {
     }
 }
 
+// `number` is part of `line`.
+fn parse_number(line: &str, number: &str) -> Result<usize, Day4Error> {
+    number
+        .parse()
+        .map_err(|err| Day4Error::ParseIntError(Token::new(line, number), err))
+}
+
+impl Day4Error {
+    fn token(&self) -> Option<&Token> {
+        match self {
+            Self::ParseIntError(number, _) => Some(number),
+            _ => None,
+        }
+    }
+}
+
 impl Display for Day4Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NoColonSeparator(line) => write!(f, "No colon separator on line \"{}\"", line),
+            Self::NoCardID(line) => write!(f, "No card ID on line \"{}\"", line),
             Self::NoPipe(line) => write!(f, "No pipe separator on line \"{}\"", line),
-            Self::ParseIntError(err) => err.fmt(f),
-            Self::IoError(err) => err.fmt(f),
+            Self::ParseIntError(number, _) => write!(f, "Bad number '{}'", number),
+            Self::OutOfOrder(expected, found) => {
+                write!(f, "Expected card {}, but found card {}", expected, found)
+            }
+            Self::PastTheEnd(id) => write!(f, "Card {} wins copies of cards that don't exist", id),
+            Self::TooLarge => write!(f, "Answer too large to fit in a number"),
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day4Error {}
+impl Error for Day4Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::ParseIntError(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
-impl From<ParseIntError> for Day4Error {
-    fn from(value: ParseIntError) -> Self {
-        Day4Error::ParseIntError(value)
+impl PuzzleError for Day4Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::OutOfOrder(..) | Self::PastTheEnd(_) => Category::InvalidPuzzle,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
     }
 }
 
@@ -121,7 +203,12 @@ impl From<IoError> for Day4Error {
 
 #[cfg(test)]
 mod tests {
-    use super::{Card, Day4Error};
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{Card, Day4Error, Puzzle, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
     use std::collections::HashSet;
 
     #[test]
@@ -140,4 +227,36 @@ mod tests {
             Err(err) => panic!("Got error {}", err),
         }
     }
+
+    #[test]
+    fn points_at_bad_number() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30";
+        let err = Puzzle::from_reader(input.as_bytes()).unwrap_err();
+        let location = err.location.unwrap();
+        assert_eq!((location.line, location.column, location.width), (2, 12, 2));
+
+        // Not the colon after the card ID.
+        let err = Puzzle::from_reader("Card 1: 1 : | 2".as_bytes()).unwrap_err();
+        assert_eq!(err.location.unwrap().column, 11);
+    }
+
+    #[test]
+    fn checks_the_cards_line_up() {
+        let repeated = "Card 1: 1 | 2\nCard 1: 1 | 1\n";
+        let err = Puzzle::from_reader(repeated.as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day4Error::OutOfOrder(2, 1)));
+        assert_eq!(err.location.unwrap().line, 2);
+
+        let past_the_end = "Card 1: 1 2 | 1 2\nCard 2: 1 | 2\n";
+        let err = Puzzle::from_reader(past_the_end.as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day4Error::PastTheEnd(1)));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(Part1::solve);
+            let _ = Puzzle::from_reader(input).map(Part2::solve);
+        });
+    }
 }
@@ -1,29 +1,53 @@
-use super::{Card, Day4Error, Puzzle};
+use super::{Card, Day4Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 4;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "13",
+    }];
+    // Cards out of order used to get an answer, now they're an invalid puzzle.
+    const VERSION: usize = 2;
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day4Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_points(input)
+    }
+}
 
 pub fn sum_points(puzzle: Puzzle) -> Result<usize, Day4Error> {
-    Ok(puzzle.0.into_iter().map(calculate_points_for_card).sum())
+    puzzle.0.into_iter().try_fold(0_usize, |sum, card| {
+        calculate_points_for_card(&card)
+            .and_then(|points| sum.checked_add(points))
+            .ok_or(Day4Error::TooLarge)
+    })
 }
 
-fn calculate_points_for_card(card: Card) -> usize {
-    let matching_numbers = card
-        .winning_numbers
-        .intersection(&card.scratched_numbers)
-        .count();
-    if matching_numbers == 0 {
-        0
-    } else {
-        2_usize.pow(matching_numbers as u32 - 1)
+// None if the points don't fit in a usize.
+pub(super) fn calculate_points_for_card(card: &Card) -> Option<usize> {
+    match card.matches() {
+        0 => Some(0),
+        matches => 1_usize.checked_shl(u32::try_from(matches - 1).ok()?),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::Puzzle;
-    use super::sum_points;
+    use super::{sum_points, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle: Puzzle = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11\n".try_into().unwrap();
+        let puzzle = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let result = sum_points(puzzle).unwrap();
         assert_eq!(result, 13);
     }
@@ -0,0 +1,106 @@
+use super::part1::calculate_points_for_card;
+use super::Puzzle;
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 4;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "explain",
+        usage: "explain CARD",
+        help: "Show which numbers a card matched, its points and the copies it wins",
+    }];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let id = query::argument::<usize>(args, "explain CARD")?;
+                let card = input
+                    .0
+                    .iter()
+                    .find(|card| card.id == id)
+                    .ok_or(QueryError::NotFound(format!("card {}", id)))?;
+
+                let mut matches = card
+                    .winning_numbers
+                    .intersection(&card.scratched_numbers)
+                    .collect::<Vec<_>>();
+                matches.sort();
+                let matches = matches
+                    .iter()
+                    .map(|number| number.to_string())
+                    .collect::<Vec<_>>();
+
+                let worth = match calculate_points_for_card(card) {
+                    Some(points) => format!("{} points", points),
+                    None => String::from("too many to count"),
+                };
+
+                Ok(match matches.len() {
+                    0 => format!("Card {}: no matches, worth nothing", id),
+                    count => format!(
+                        "Card {}: {} matching ({}), worth {}\nwins copies of cards {} to {}",
+                        id,
+                        count,
+                        matches.join(", "),
+                        worth,
+                        id + 1,
+                        id + count
+                    ),
+                })
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_card() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["1"]).unwrap(),
+            "Card 1: 4 matching (17, 48, 83, 86), worth 8 points\nwins copies of cards 2 to 5"
+        );
+        assert_eq!(
+            Queries::query(&input, "explain", &["5"]).unwrap(),
+            "Card 5: no matches, worth nothing"
+        );
+    }
+
+    #[test]
+    fn doesnt_overflow_on_big_cards() {
+        // Card 1 matches 65 numbers, and there are enough cards after it for the copies.
+        let numbers = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut input = format!("Card 1: {} | {}\n", numbers, numbers);
+        for id in 2..=66 {
+            input.push_str(&format!("Card {}: 1 | 2\n", id));
+        }
+        let input = Puzzle::from_reader(input.as_bytes()).unwrap();
+        let explained = Queries::query(&input, "explain", &["1"]).unwrap();
+        assert!(explained.starts_with("Card 1: 65 matching (1, 2, "));
+        assert!(explained.ends_with("worth too many to count\nwins copies of cards 2 to 66"));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
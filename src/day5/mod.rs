@@ -1,12 +1,19 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error as IoError};
+use std::io::{BufRead, Error as IoError};
 use std::num::ParseIntError;
 
+pub const TITLE: &str = "If You Give A Seed A Fertilizer";
+
+pub const EXAMPLE: &str = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4\n";
+
 #[derive(Debug, PartialEq)]
 pub struct Puzzle {
     seeds: Vec<isize>,
@@ -14,77 +21,94 @@ pub struct Puzzle {
 }
 
 #[derive(Debug, PartialEq)]
-struct Map {
+pub struct Map {
     from: String,
     to: String,
     entries: Vec<MapEntry>,
 }
 
-impl Map {
-    fn from_buf_lines<Iter: Iterator<Item = Result<String, IoError>>>(
-        lines: &mut Iter,
-    ) -> Result<Self, Day5Error> {
-        let header = lines.next().ok_or(Day5Error::NoMapHeader)??;
-        let (from, to) = header[0..header.len() - 5]
-            .split_once("-to-")
-            .ok_or(Day5Error::InvalidMapHeader)?;
+impl Puzzle {
+    pub fn seeds(&self) -> &[isize] {
+        &self.seeds
+    }
 
-        let mut entries = Vec::<MapEntry>::new();
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
+    }
 
-        for line in lines {
-            let line = line?;
-            if line.is_empty() {
-                break;
+    // The maps from seed to location, following each one's "to" into the next one's "from".
+    pub fn maps_in_order(&self) -> Result<Vec<&Map>, Day5Error> {
+        let mut maps = vec![];
+        let mut stage = "seed";
+        while stage != "location" {
+            let map = self
+                .maps
+                .iter()
+                .find(|map| map.from == stage)
+                .ok_or_else(|| Day5Error::MissingMap(String::from(stage)))?;
+            // A map that leads back somewhere we've been would go round forever.
+            if maps.len() == self.maps.len() {
+                return Err(Day5Error::CircularMaps);
             }
+            maps.push(map);
+            stage = &map.to;
+        }
+        Ok(maps)
+    }
+}
 
-            let nums = line
-                .split(' ')
-                .map(|num| num.parse::<isize>())
-                .collect::<Result<Vec<_>, _>>()?;
-            if nums.len() != 3 {
-                return Err(Day5Error::InvalidRange);
-            }
+impl Map {
+    // What kind of number this map takes, like "seed" for a seed-to-soil map.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
 
-            entries.push(MapEntry {
-                destination: Range::from_size(nums[0], nums[2]),
-                source: Range::from_size(nums[1], nums[2]),
-            });
-        }
+    pub fn to(&self) -> &str {
+        &self.to
+    }
 
-        Ok(Map {
-            from: String::from(from),
-            to: String::from(to),
-            entries,
-        })
+    pub fn entries(&self) -> &[MapEntry] {
+        &self.entries
     }
 
-    fn from_str_lines<'a, Iter: Iterator<Item = &'a str>>(
+    fn from_lines<Iter: Iterator<Item = (usize, Result<String, IoError>)>>(
         lines: &mut Iter,
-    ) -> Result<Self, Day5Error> {
-        let header = lines.next().ok_or(Day5Error::NoMapHeader)?;
-        let (from, to) = header[0..header.len() - 5]
-            .split_once("-to-")
-            .ok_or(Day5Error::InvalidMapHeader)?;
+    ) -> Result<Self, Located<Day5Error>> {
+        let (index, header) = lines.next().ok_or(Day5Error::NoMapHeader)?;
+        let header = header.map_err(Day5Error::from)?;
+        let (from, to) = header
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+            .ok_or_else(|| {
+                Located::new(
+                    Day5Error::InvalidMapHeader,
+                    Location::new(index, &header, None),
+                )
+            })?;
 
         let mut entries = Vec::<MapEntry>::new();
 
-        for line in lines {
+        for (index, line) in lines {
+            let line = line.map_err(Day5Error::from)?;
             if line.trim().is_empty() {
                 break;
             }
 
-            let nums = line
-                .split(' ')
-                .map(|num| num.parse::<isize>())
-                .collect::<Result<Vec<_>, _>>()?;
-            if nums.len() != 3 {
-                return Err(Day5Error::InvalidRange);
-            }
+            let nums = parse_numbers(index, &line, &line)?;
+            let entry = match nums[..] {
+                [destination, source, size] => Range::checked_from_size(destination, size)
+                    .zip(Range::checked_from_size(source, size))
+                    .map(|(destination, source)| MapEntry {
+                        destination,
+                        source,
+                    }),
+                _ => None,
+            };
+            let entry = entry.ok_or_else(|| {
+                Located::new(Day5Error::InvalidRange, Location::new(index, &line, None))
+            })?;
 
-            entries.push(MapEntry {
-                destination: Range::from_size(nums[0], nums[2]),
-                source: Range::from_size(nums[1], nums[2]),
-            });
+            entries.push(entry);
         }
 
         Ok(Map {
@@ -96,13 +120,22 @@ impl Map {
 }
 
 #[derive(Debug, PartialEq)]
-struct MapEntry {
+pub struct MapEntry {
     source: Range,
     destination: Range,
 }
 
 impl MapEntry {
-    fn map_number(&self, num: isize) -> isize {
+    pub fn source(&self) -> &Range {
+        &self.source
+    }
+
+    pub fn destination(&self) -> &Range {
+        &self.destination
+    }
+
+    // Numbers outside the source range are left as they are.
+    pub fn map_number(&self, num: isize) -> isize {
         if self.source.contains_value(num) {
             num - self.source.from + self.destination.from
         } else {
@@ -110,7 +143,9 @@ impl MapEntry {
         }
     }
 
-    fn map_range(&self, range: &Range) -> (Range, Option<Range>, Option<Range>) {
+    // The part of `range` this entry covers, mapped, along with whatever's left over on either
+    // side of it. Only makes sense when the source overlaps `range`.
+    pub fn map_range(&self, range: &Range) -> (Range, Option<Range>, Option<Range>) {
         if self.source.contains_range(range) {
             // Self  |---------|   |-----|   |-----|   |-----|
             // Other    |---|      |---|       |---|   |-----|
@@ -184,7 +219,7 @@ impl MapEntry {
 }
 
 #[derive(Debug, PartialEq)]
-struct Range {
+pub struct Range {
     // Inclusive
     from: isize,
     // Also inclusive
@@ -192,22 +227,47 @@ struct Range {
 }
 
 impl Range {
-    fn from_size(start: isize, size: isize) -> Self {
+    // The `size` numbers starting at `start`, or None if there aren't any or the end doesn't fit
+    // in an isize. Numbers straight out of the input go through here.
+    pub fn checked_from_size(start: isize, size: isize) -> Option<Self> {
+        if size < 1 {
+            return None;
+        }
+        let to = start.checked_add(size)?.checked_sub(1)?;
+        Some(Self { from: start, to })
+    }
+
+    // Everything from `from` to `to`, both included, or None if they're the wrong way round.
+    pub fn new(from: isize, to: isize) -> Option<Self> {
+        (from <= to).then_some(Self { from, to })
+    }
+
+    // Unchecked, for the tests' sizes that are known to be fine.
+    #[cfg(test)]
+    pub(crate) fn from_size(start: isize, size: isize) -> Self {
         Self {
             from: start,
             to: start + size - 1,
         }
     }
 
-    fn contains_value(&self, num: isize) -> bool {
+    pub fn from(&self) -> isize {
+        self.from
+    }
+
+    pub fn to(&self) -> isize {
+        self.to
+    }
+
+    pub fn contains_value(&self, num: isize) -> bool {
         num >= self.from && num <= self.to
     }
 
-    fn contains_range(&self, range: &Self) -> bool {
+    pub fn contains_range(&self, range: &Self) -> bool {
         self.contains_value(range.from) && self.contains_value(range.to)
     }
 
-    fn overlaps(&self, range: &Self) -> bool {
+    pub fn overlaps(&self, range: &Self) -> bool {
         self.contains_value(range.from) || self.contains_value(range.to)
     }
 }
@@ -218,53 +278,26 @@ pub enum Day5Error {
     NoMapHeader,
     InvalidMapHeader,
     InvalidRange,
+    // Nothing maps this kind of number any further, and it isn't a location yet.
+    MissingMap(String),
+    // The maps lead back to somewhere they've already been before getting to a location.
+    CircularMaps,
     IoError(IoError),
-    ParseIntError(ParseIntError),
-}
-
-impl TryFrom<File> for Puzzle {
-    type Error = Day5Error;
-
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let buf = BufReader::new(value);
-        let mut lines = buf.lines().peekable();
-
-        let seeds = lines.next().ok_or(Day5Error::NoSeeds)??;
-        if !seeds.starts_with("seeds: ") {
-            return Err(Day5Error::NoSeeds);
-        }
-        let seeds = seeds[7..]
-            .split(' ')
-            .map(|num| num.parse::<isize>())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Just gotta skip the empty line.
-        let _ = lines.next();
-
-        let mut maps = Vec::<Map>::new();
-
-        while lines.peek().is_some() {
-            maps.push(Map::from_buf_lines(&mut lines)?);
-        }
-
-        Ok(Puzzle { seeds, maps })
-    }
+    ParseIntError(String, ParseIntError),
 }
 
-impl TryFrom<&str> for Puzzle {
-    type Error = Day5Error;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day5Error>;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut lines = value.lines().peekable();
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let mut lines = input::lines(reader).peekable();
 
-        let seeds = lines.next().ok_or(Day5Error::NoSeeds)?;
-        if !seeds.starts_with("seeds: ") {
-            return Err(Day5Error::NoSeeds);
-        }
-        let seeds = seeds[7..]
-            .split(' ')
-            .map(|num| num.parse::<isize>())
-            .collect::<Result<Vec<_>, _>>()?;
+        let (index, line) = lines.next().ok_or(Day5Error::NoSeeds)?;
+        let line = line.map_err(Day5Error::from)?;
+        let seeds = line
+            .strip_prefix("seeds: ")
+            .ok_or_else(|| Located::new(Day5Error::NoSeeds, Location::new(index, &line, None)))?;
+        let seeds = parse_numbers(index, &line, seeds)?;
 
         // Just gotta skip the empty line.
         let _ = lines.next();
@@ -272,10 +305,12 @@ impl TryFrom<&str> for Puzzle {
         let mut maps = Vec::<Map>::new();
 
         while lines.peek().is_some() {
-            maps.push(Map::from_str_lines(&mut lines)?);
+            maps.push(Map::from_lines(&mut lines)?);
         }
 
-        Ok(Puzzle { seeds, maps })
+        let puzzle = Puzzle { seeds, maps };
+        puzzle.maps_in_order()?;
+        Ok(puzzle)
     }
 }
 
@@ -285,10 +320,23 @@ impl From<IoError> for Day5Error {
     }
 }
 
-impl From<ParseIntError> for Day5Error {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
-    }
+// Parses the space-separated numbers in `numbers`, which is part of `line`.
+fn parse_numbers(
+    index: usize,
+    line: &str,
+    numbers: &str,
+) -> Result<Vec<isize>, Located<Day5Error>> {
+    numbers
+        .split(' ')
+        .map(|num| {
+            num.parse::<isize>().map_err(|err| {
+                Located::new(
+                    Day5Error::ParseIntError(String::from(num), err),
+                    Location::new(index, line, Some(&Token::new(line, num))),
+                )
+            })
+        })
+        .collect()
 }
 
 impl Display for Day5Error {
@@ -298,17 +346,42 @@ impl Display for Day5Error {
             Self::NoMapHeader => write!(f, "No map header!"),
             Self::InvalidMapHeader => write!(f, "Invalid map header!"),
             Self::InvalidRange => write!(f, "Invalid range!"),
-            Self::IoError(err) => err.fmt(f),
-            Self::ParseIntError(err) => err.fmt(f),
+            Self::MissingMap(stage) => write!(f, "No map from {} to anything!", stage),
+            Self::CircularMaps => write!(f, "The maps go round in a circle!"),
+            Self::IoError(_) => write!(f, "I/O error"),
+            Self::ParseIntError(num, _) => write!(f, "Bad number '{}'", num),
+        }
+    }
+}
+
+impl Error for Day5Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::ParseIntError(_, err) => Some(err),
+            _ => None,
         }
     }
 }
 
-impl Error for Day5Error {}
+impl PuzzleError for Day5Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::MissingMap(_) | Self::CircularMaps => Category::InvalidPuzzle,
+            _ => Category::Parse,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use super::{Map, MapEntry, Puzzle, Range};
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{Day5Error, Map, MapEntry, Puzzle, Range, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
 
     const MAP: MapEntry = MapEntry {
         source: Range { from: 2, to: 8 },
@@ -437,8 +510,8 @@ mod tests {
                 },
             ],
         };
-        let input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4\n";
-        let result: Result<Puzzle, _> = input.try_into();
+        let input = EXAMPLE;
+        let result = Puzzle::from_reader(input.as_bytes());
         match result {
             Ok(puzzle) => assert_eq!(puzzle, expected),
             Err(err) => panic!("Got error {}", err),
@@ -569,4 +642,39 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn points_at_bad_range() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50\n";
+        let err = Puzzle::from_reader(input.as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day5Error::InvalidRange));
+        let location = err.location.unwrap();
+        assert_eq!((location.line, location.column), (5, 1));
+        assert_eq!(location.source, "52 50");
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(Part1::solve);
+            let _ = Puzzle::from_reader(input).map(Part2::solve);
+        });
+    }
+
+    #[test]
+    fn needs_a_way_to_location() {
+        let err = Puzzle::from_reader("seeds: 1".as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day5Error::MissingMap(stage) if stage == "seed"));
+
+        let circle = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n1 2 3\n";
+        let err = Puzzle::from_reader(circle.as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day5Error::CircularMaps));
+    }
+
+    #[test]
+    fn rejects_ranges_that_overflow() {
+        let input = "seeds: 1\n\na-to-b map:\n9223372036854775807 0 2\n";
+        let err = Puzzle::from_reader(input.as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day5Error::InvalidRange));
+    }
 }
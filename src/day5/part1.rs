@@ -1,11 +1,30 @@
-use super::{Day5Error, Puzzle};
+use super::{Day5Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 5;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "35",
+    }];
+
+    type Input = Puzzle;
+    type Output = isize;
+    type Error = Day5Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        get_lowest_location(input)
+    }
+}
 
 pub fn get_lowest_location(puzzle: Puzzle) -> Result<isize, Day5Error> {
     let mut numbers = puzzle.seeds.clone();
-    let mut stage = "seed";
 
-    while stage != "location" {
-        let map = puzzle.maps.iter().find(|map| map.from == stage).unwrap();
+    for map in puzzle.maps_in_order()? {
         numbers = numbers
             .into_iter()
             .map(|num| {
@@ -20,20 +39,20 @@ pub fn get_lowest_location(puzzle: Puzzle) -> Result<isize, Day5Error> {
                 }
             })
             .collect::<Vec<_>>();
-        stage = &map.to[..];
     }
 
-    Ok(numbers.into_iter().min().unwrap())
+    numbers.into_iter().min().ok_or(Day5Error::NoSeeds)
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::Puzzle;
-    use super::get_lowest_location;
+    use super::{get_lowest_location, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let input: Puzzle = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4\n".try_into().unwrap();
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let result = get_lowest_location(input);
         match result {
             Ok(answer) => assert_eq!(answer, 35),
@@ -0,0 +1,115 @@
+use super::part2::{get_seed_ranges, map_range_recursive};
+use super::{Map, Puzzle};
+use crate::query::{self, Command, Query, QueryError};
+use std::cmp::Reverse;
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 5;
+    const COMMANDS: &'static [Command] = &[
+        Command {
+            name: "explain",
+            usage: "explain SEED",
+            help: "Follow a seed through every map to its location",
+        },
+        Command {
+            name: "fragments",
+            usage: "fragments",
+            help: "Count how many pieces each part 2 seed range ends up split into",
+        },
+    ];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let mut number = query::argument::<isize>(args, "explain SEED")?;
+                let mut steps = vec![format!("seed {}", number)];
+                for map in maps_in_order(input)? {
+                    number = map
+                        .entries
+                        .iter()
+                        .find(|entry| entry.source.contains_value(number))
+                        .map_or(number, |entry| entry.map_number(number));
+                    steps.push(format!("{} {}", map.to, number));
+                }
+                Ok(steps.join(" -> "))
+            }
+            "fragments" => {
+                if !args.is_empty() {
+                    return Err(QueryError::Usage("fragments"));
+                }
+
+                let maps = maps_in_order(input)?;
+                let seeds = get_seed_ranges(&input.seeds)
+                    .map_err(|_| QueryError::NotFound(String::from("valid seed range")))?;
+                let mut fragments = seeds
+                    .into_iter()
+                    .map(|seeds| {
+                        let name = format!("{}-{}", seeds.from, seeds.to);
+                        let ranges = maps.iter().fold(vec![seeds], |ranges, map| {
+                            ranges
+                                .into_iter()
+                                .flat_map(|range| map_range_recursive(&map.entries, range))
+                                .collect()
+                        });
+                        (ranges.len(), name)
+                    })
+                    .collect::<Vec<_>>();
+                // Most fragmented first.
+                fragments.sort_by_key(|(count, _)| Reverse(*count));
+
+                Ok(fragments
+                    .iter()
+                    .map(|(count, name)| format!("seeds {}: {} ranges", name, count))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+// Parsing already made sure there's a way from seed to location.
+fn maps_in_order(input: &Puzzle) -> Result<Vec<&Map>, QueryError> {
+    input
+        .maps_in_order()
+        .map_err(|_| QueryError::NotFound(String::from("way to location")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_seed() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["79"]).unwrap(),
+            "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82"
+        );
+    }
+
+    #[test]
+    fn counts_fragments() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        let fragments = Queries::query(&input, "fragments", &[]).unwrap();
+        assert_eq!(fragments.lines().count(), 2);
+        assert!(fragments.starts_with("seeds "));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
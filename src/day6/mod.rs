@@ -1,95 +1,135 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error as IoError};
+use std::io::{BufRead, Error as IoError};
 use std::num::ParseIntError;
 
+pub const TITLE: &str = "Wait For It";
+
+pub const EXAMPLE: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
 #[derive(Debug, PartialEq)]
 pub struct Puzzle {
     times: Vec<isize>,
     distances: Vec<isize>,
 }
 
+impl Puzzle {
+    pub fn times(&self) -> &[isize] {
+        &self.times
+    }
+
+    pub fn distances(&self) -> &[isize] {
+        &self.distances
+    }
+}
+
+// How many ways there are to beat `distance` in a race that lasts `time`.
+fn count_ways(time: isize, distance: isize) -> usize {
+    winning_holds(time, distance).map_or(0, |(first, last)| (last - first + 1) as usize)
+}
+
+// The shortest and longest hold times that beat `distance`, if any do. Holding the button for
+// `i` goes `i * (time - i)`, which goes up until halfway and then back down the same way, so it's
+// enough to find the first hold time that wins and mirror it. Times can be huge (especially in
+// part 2), so it's a binary search instead of trying them all, and the distances are worked out
+// in i128 since they can be around `time` squared.
+fn winning_holds(time: isize, distance: isize) -> Option<(isize, isize)> {
+    let travelled = |hold: isize| hold as i128 * (time - hold) as i128;
+    let halfway = time / 2;
+    if time < 2 || travelled(halfway) <= distance as i128 {
+        return None;
+    }
+
+    // The first hold time that wins is somewhere in low..=high.
+    let (mut low, mut high) = (1, halfway);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if travelled(middle) > distance as i128 {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    Some((low, time - low))
+}
+
 #[derive(Debug)]
 pub enum Day6Error {
     NoTimes,
     NoDistances,
-    ParseIntError(ParseIntError),
+    ParseIntError(String, ParseIntError),
+    TooLarge,
     IoError(IoError),
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = Day6Error;
-
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let buf = BufReader::new(value);
-        let mut lines = buf.lines();
-
-        let times = lines
-            .next()
-            .ok_or(Day6Error::NoTimes)??
-            .split_whitespace()
-            .skip(1)
-            .map(|num| num.parse())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let distances = lines
-            .next()
-            .ok_or(Day6Error::NoDistances)??
-            .split_whitespace()
-            .skip(1)
-            .map(|num| num.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day6Error>;
 
-        Ok(Puzzle { times, distances })
-    }
-}
-
-impl TryFrom<&str> for Puzzle {
-    type Error = Day6Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut lines = value.lines();
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let mut lines = input::records(reader);
 
-        let times = lines
-            .next()
-            .ok_or(Day6Error::NoTimes)?
-            .split_whitespace()
-            .skip(1)
-            .map(|num| num.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+        let (index, times) = lines.next().ok_or(Day6Error::NoTimes)?;
+        let times = parse_numbers(index, &times.map_err(Day6Error::from)?)?;
 
-        let distances = lines
-            .next()
-            .ok_or(Day6Error::NoDistances)?
-            .split_whitespace()
-            .skip(1)
-            .map(|num| num.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+        let (index, distances) = lines.next().ok_or(Day6Error::NoDistances)?;
+        let distances = parse_numbers(index, &distances.map_err(Day6Error::from)?)?;
 
         Ok(Puzzle { times, distances })
     }
 }
 
+// Everything after the "Time:" or "Distance:" label.
+fn parse_numbers(index: usize, line: &str) -> Result<Vec<isize>, Located<Day6Error>> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|num| {
+            num.parse().map_err(|err| {
+                Located::new(
+                    Day6Error::ParseIntError(String::from(num), err),
+                    Location::new(index, line, Some(&Token::new(line, num))),
+                )
+            })
+        })
+        .collect()
+}
+
 impl Display for Day6Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NoTimes => write!(f, "No line for times!"),
             Self::NoDistances => write!(f, "No line for distances!"),
-            Self::ParseIntError(err) => err.fmt(f),
-            Self::IoError(err) => err.fmt(f),
+            Self::ParseIntError(num, _) => write!(f, "Bad number '{}'", num),
+            Self::TooLarge => write!(f, "Answer too large to fit in a number"),
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day6Error {}
+impl Error for Day6Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::ParseIntError(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
-impl From<ParseIntError> for Day6Error {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
+impl PuzzleError for Day6Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
     }
 }
 
@@ -101,7 +141,12 @@ impl From<IoError> for Day6Error {
 
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{count_ways, Puzzle, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
 
     #[test]
     fn parse_provided_example() {
@@ -109,8 +154,26 @@ mod tests {
             times: vec![7, 15, 30],
             distances: vec![9, 40, 200],
         };
-        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
-        let puzzle: Puzzle = input.try_into().unwrap();
+        let input = EXAMPLE;
+        let puzzle = Puzzle::from_reader(input.as_bytes()).unwrap();
         assert_eq!(puzzle, expected);
     }
+
+    #[test]
+    fn counts_ways_without_trying_them_all() {
+        assert_eq!(count_ways(7, 9), 4);
+        assert_eq!(count_ways(30, 200), 9);
+        assert_eq!(count_ways(71530, 940200), 71503);
+        assert_eq!(count_ways(4, 4), 0);
+        assert_eq!(count_ways(1, -1), 0);
+        assert_eq!(count_ways(isize::MAX, isize::MAX), isize::MAX as usize - 3);
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(Part1::solve);
+            let _ = Puzzle::from_reader(input).map(Part2::solve);
+        });
+    }
 }
@@ -1,32 +1,46 @@
-use super::{Day6Error, Puzzle};
+use super::{count_ways, Day6Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
 
-pub fn multiply_winning_ways(puzzle: Puzzle) -> Result<isize, Day6Error> {
-    Ok(puzzle
-        .times
-        .iter()
-        .zip(puzzle.distances.iter())
-        .map(|(time, distance)| {
-            let mut ways = 0;
-            for i in 1..*time {
-                if i * (time - i) > *distance {
-                    ways += 1;
-                }
-            }
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 6;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "288",
+    }];
 
-            ways
-        })
-        .product::<isize>())
+    type Input = Puzzle;
+    type Output = isize;
+    type Error = Day6Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        multiply_winning_ways(input)
+    }
+}
+
+pub fn multiply_winning_ways(puzzle: Puzzle) -> Result<isize, Day6Error> {
+    puzzle.times.iter().zip(puzzle.distances.iter()).try_fold(
+        1_isize,
+        |product, (time, distance)| {
+            isize::try_from(count_ways(*time, *distance))
+                .ok()
+                .and_then(|ways| product.checked_mul(ways))
+                .ok_or(Day6Error::TooLarge)
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{multiply_winning_ways, Puzzle};
+    use super::{multiply_winning_ways, Puzzle, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle: Puzzle = "Time:      7  15   30\nDistance:  9  40  200\n"
-            .try_into()
-            .unwrap();
+        let puzzle = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let ways = multiply_winning_ways(puzzle).unwrap();
         assert_eq!(ways, 288);
     }
@@ -1,44 +1,59 @@
-use super::{Day6Error, Puzzle};
-
-pub fn count_winning_ways(puzzle: Puzzle) -> Result<usize, Day6Error> {
-    let time = concatenate_numbers(&puzzle.times);
-    let distance = concatenate_numbers(&puzzle.distances);
-    let mut ways: usize = 0;
-
-    for i in 1..time {
-        if i * (time - i) > distance {
-            ways += 1;
-        }
+use super::{count_ways, Day6Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 6;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "71503",
+    }];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day6Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        count_winning_ways(input)
     }
+}
 
-    Ok(ways)
+pub fn count_winning_ways(puzzle: Puzzle) -> Result<usize, Day6Error> {
+    let time = concatenate_numbers(&puzzle.times).ok_or(Day6Error::TooLarge)?;
+    let distance = concatenate_numbers(&puzzle.distances).ok_or(Day6Error::TooLarge)?;
+    Ok(count_ways(time, distance))
 }
 
-fn concatenate_numbers(nums: &[isize]) -> isize {
-    nums.iter()
-        .fold(0, |acc, val| acc * get_digit_multiplier(*val) + val)
+// None if the digits don't all fit in one number.
+fn concatenate_numbers(nums: &[isize]) -> Option<isize> {
+    nums.iter().try_fold(0_isize, |acc, val| {
+        acc.checked_mul(get_digit_multiplier(*val)?)?
+            .checked_add(*val)
+    })
 }
 
-fn get_digit_multiplier(num: isize) -> isize {
-    let mut multiplier = 10;
+fn get_digit_multiplier(num: isize) -> Option<isize> {
+    let mut multiplier: isize = 10;
     let mut num = num;
     while num >= 10 {
         num /= 10;
-        multiplier *= 10;
+        multiplier = multiplier.checked_mul(10)?;
     }
 
-    multiplier
+    Some(multiplier)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{count_winning_ways, Puzzle};
+    use super::{count_winning_ways, Puzzle, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle: Puzzle = "Time:      7  15   30\nDistance:  9  40  200\n"
-            .try_into()
-            .unwrap();
+        let puzzle = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let ways = count_winning_ways(puzzle).unwrap();
         assert_eq!(ways, 71503);
     }
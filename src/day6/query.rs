@@ -0,0 +1,81 @@
+use super::{winning_holds, Puzzle};
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 6;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "explain",
+        usage: "explain RACE",
+        help: "Show how long the button can be held to beat a race's record",
+    }];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let race = query::argument::<usize>(args, "explain RACE")?;
+                let (time, distance) = race
+                    .checked_sub(1)
+                    .and_then(|index| Some((input.times.get(index)?, input.distances.get(index)?)))
+                    .ok_or(QueryError::NotFound(format!("race {}", race)))?;
+
+                Ok(match winning_holds(*time, *distance) {
+                    Some((first, last)) => format!(
+                        "Race {}: {} ms, record {} mm\nhold for {} to {} ms, {} ways to win",
+                        race,
+                        time,
+                        distance,
+                        first,
+                        last,
+                        last - first + 1
+                    ),
+                    None => format!(
+                        "Race {}: {} ms, record {} mm\nno way to win",
+                        race, time, distance
+                    ),
+                })
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_race() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["2"]).unwrap(),
+            "Race 2: 15 ms, record 40 mm\nhold for 4 to 11 ms, 8 ways to win"
+        );
+    }
+
+    #[test]
+    fn explains_huge_races() {
+        let input = "Time: 9223372036854775807\nDistance: 1\n";
+        let input = Puzzle::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["1"]).unwrap(),
+            "Race 1: 9223372036854775807 ms, record 1 mm\nhold for 1 to 9223372036854775806 ms, 9223372036854775806 ways to win"
+        );
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
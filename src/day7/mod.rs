@@ -1,25 +1,54 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::cmp::Ordering;
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error as IoError};
+use std::io::{BufRead, Error as IoError};
 use std::num::ParseIntError;
 
+pub const TITLE: &str = "Camel Cards";
+
+pub const EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+
 #[derive(Debug, PartialEq)]
 pub struct Puzzle(Vec<Hand>);
 
 #[derive(Debug, Eq, PartialEq)]
-struct Hand {
+pub struct Hand {
     cards: Vec<Card>,
     hand_type: Option<HandType>,
     bet: usize,
 }
 
+impl Puzzle {
+    pub fn hands(&self) -> &[Hand] {
+        &self.0
+    }
+}
+
+impl Hand {
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    // Which type a hand is depends on whether jacks are jokers, so it's only filled in once a
+    // part has decided.
+    pub fn hand_type(&self) -> Option<&HandType> {
+        self.hand_type.as_ref()
+    }
+
+    pub fn bet(&self) -> usize {
+        self.bet
+    }
+}
+
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
-enum HandType {
+pub enum HandType {
     FiveOfAKind = 7,
     FourOfAKind = 6,
     FullHouse = 5,
@@ -30,7 +59,7 @@ enum HandType {
 }
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-enum Card {
+pub enum Card {
     Ace = 14,
     King = 13,
     Queen = 12,
@@ -48,32 +77,27 @@ enum Card {
 
 #[derive(Debug)]
 pub enum Day7Error {
-    UnrecognizedCharacter(char),
+    UnrecognizedCharacter(Token),
     UnparsableHand(String),
-    ParseIntError(ParseIntError),
+    WrongHandSize(String),
+    ParseIntError(Token, ParseIntError),
+    TooLarge,
     IoError(IoError),
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = Day7Error;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day7Error>;
 
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let buf = BufReader::new(value);
-        let lines = buf.lines();
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let lines = input::records(reader);
         let hands = lines
-            .map(|line| (&line?[..]).try_into())
-            .collect::<Result<Vec<Hand>, _>>()?;
-        Ok(Self(hands))
-    }
-}
-
-impl TryFrom<&str> for Puzzle {
-    type Error = Day7Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let lines = value.lines();
-        let hands = lines
-            .map(|line| line.try_into())
+            .map(|(index, line)| {
+                let line = line.map_err(Day7Error::from)?;
+                Hand::try_from(&line[..]).map_err(|err| {
+                    let location = Location::new(index, &line, err.token());
+                    Located::new(err, location)
+                })
+            })
             .collect::<Result<Vec<Hand>, _>>()?;
         Ok(Self(hands))
     }
@@ -87,11 +111,22 @@ impl TryFrom<&str> for Hand {
             .split_once(' ')
             .ok_or(Day7Error::UnparsableHand(value.to_string()))?;
         let cards = cards
-            .chars()
-            .map(|char| char.try_into())
+            .char_indices()
+            .map(|(start, char)| {
+                Card::try_from(char).map_err(|_| {
+                    let card = &cards[start..start + char.len_utf8()];
+                    Day7Error::UnrecognizedCharacter(Token::new(value, card))
+                })
+            })
             .collect::<Result<Vec<Card>, _>>()?;
+        // Every hand type is worked out assuming exactly five cards.
+        if cards.len() != 5 {
+            return Err(Day7Error::WrongHandSize(String::from(value)));
+        }
 
-        let bet: usize = bet.parse()?;
+        let bet: usize = bet
+            .parse()
+            .map_err(|err| Day7Error::ParseIntError(Token::new(value, bet), err))?;
 
         Ok(Self {
             cards,
@@ -125,8 +160,9 @@ impl Ord for Hand {
     }
 }
 
+// The error is whatever character isn't a card.
 impl TryFrom<char> for Card {
-    type Error = Day7Error;
+    type Error = char;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -143,7 +179,20 @@ impl TryFrom<char> for Card {
             '4' => Ok(Card::Four),
             '3' => Ok(Card::Three),
             '2' => Ok(Card::Two),
-            any => Err(Self::Error::UnrecognizedCharacter(any)),
+            any => Err(any),
+        }
+    }
+}
+
+impl Day7Error {
+    fn token(&self) -> Option<&Token> {
+        match self {
+            Self::UnrecognizedCharacter(card) => Some(card),
+            Self::ParseIntError(bet, _) => Some(bet),
+            Self::UnparsableHand(_)
+            | Self::WrongHandSize(_)
+            | Self::TooLarge
+            | Self::IoError(_) => None,
         }
     }
 }
@@ -153,17 +202,31 @@ impl Display for Day7Error {
         match self {
             Self::UnrecognizedCharacter(cha) => write!(f, "Unrecognized character '{}'", cha),
             Self::UnparsableHand(hand) => write!(f, "Unparsable hand '{}'", hand),
-            Self::ParseIntError(err) => err.fmt(f),
-            Self::IoError(err) => err.fmt(f),
+            Self::WrongHandSize(hand) => write!(f, "Hand '{}' doesn't have five cards", hand),
+            Self::ParseIntError(bet, _) => write!(f, "Bad bet '{}'", bet),
+            Self::TooLarge => write!(f, "Total winnings too large to fit in a number"),
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day7Error {}
+impl Error for Day7Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::ParseIntError(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
-impl From<ParseIntError> for Day7Error {
-    fn from(value: ParseIntError) -> Self {
-        Day7Error::ParseIntError(value)
+impl PuzzleError for Day7Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
     }
 }
 
@@ -175,7 +238,12 @@ impl From<IoError> for Day7Error {
 
 #[cfg(test)]
 mod tests {
-    use super::{Card, Hand, Puzzle};
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{Card, Day7Error, Hand, Puzzle, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
 
     #[test]
     fn provided_example() {
@@ -206,8 +274,30 @@ mod tests {
                 bet: 483,
             },
         ]);
-        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
-        let puzzle: Puzzle = input.try_into().unwrap();
+        let input = EXAMPLE;
+        let puzzle = Puzzle::from_reader(input.as_bytes()).unwrap();
         assert_eq!(puzzle, expected);
     }
+
+    #[test]
+    fn points_at_bad_card() {
+        let input = "32T3K 765\nT55X5 684\n";
+        let err = Puzzle::from_reader(input.as_bytes()).unwrap_err();
+        let location = err.location.unwrap();
+        assert_eq!((location.line, location.column, location.width), (2, 4, 1));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(Part1::solve);
+            let _ = Puzzle::from_reader(input).map(Part2::solve);
+        });
+    }
+
+    #[test]
+    fn rejects_short_hands() {
+        let err = Puzzle::from_reader("32T3 765\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day7Error::WrongHandSize(_)));
+    }
 }
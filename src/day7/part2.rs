@@ -1,6 +1,27 @@
 use std::collections::HashSet;
 
-use super::{Card, Day7Error, Hand, HandType, Puzzle};
+use super::{Card, Day7Error, Hand, HandType, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 7;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "5905",
+    }];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day7Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        calculate_total_winnings(input)
+    }
+}
 
 pub fn calculate_total_winnings(mut puzzle: Puzzle) -> Result<usize, Day7Error> {
     puzzle
@@ -8,15 +29,17 @@ pub fn calculate_total_winnings(mut puzzle: Puzzle) -> Result<usize, Day7Error>
         .iter_mut()
         .for_each(|hand: &mut Hand| hand.hand_type = Some(decide_hand_type(&hand.cards)));
     puzzle.0.sort();
-    Ok(puzzle
+    puzzle
         .0
         .into_iter()
         .enumerate()
-        .map(|(rank, hand)| (rank + 1) * hand.bet)
-        .sum::<usize>())
+        .try_fold(0_usize, |sum, (rank, hand)| {
+            sum.checked_add((rank + 1).checked_mul(hand.bet)?)
+        })
+        .ok_or(Day7Error::TooLarge)
 }
 
-fn decide_hand_type(cards: &[Card]) -> HandType {
+pub(super) fn decide_hand_type(cards: &[Card]) -> HandType {
     let num_jokers = cards.iter().filter(|card| **card == Card::Jack).count();
     let non_joker_types =
         HashSet::<Card>::from_iter(cards.iter().filter(|card| **card != Card::Jack).cloned());
@@ -72,13 +95,12 @@ fn decide_hand_type(cards: &[Card]) -> HandType {
 
 #[cfg(test)]
 mod tests {
-    use super::{calculate_total_winnings, Puzzle};
+    use super::{calculate_total_winnings, Puzzle, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn provided_example() {
-        let puzzle: Puzzle = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n"
-            .try_into()
-            .unwrap();
+        let puzzle = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let result = calculate_total_winnings(puzzle).unwrap();
         assert_eq!(result, 5905);
     }
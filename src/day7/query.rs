@@ -0,0 +1,135 @@
+use super::{part1, part2, Card, Hand, Puzzle};
+use crate::query::{self, Command, Query, QueryError};
+use std::collections::HashMap;
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 7;
+    const COMMANDS: &'static [Command] = &[
+        Command {
+            name: "explain",
+            usage: "explain LINE",
+            help: "Show what type a hand is with jacks and with jokers",
+        },
+        Command {
+            name: "ties",
+            usage: "ties",
+            help: "List the hands that can't be told apart because they hold the same cards",
+        },
+    ];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let line = query::argument::<usize>(args, "explain LINE")?;
+                let hand = line
+                    .checked_sub(1)
+                    .and_then(|index| input.0.get(index))
+                    .ok_or(QueryError::NotFound(format!("hand on line {}", line)))?;
+
+                Ok(format!(
+                    "{} bets {}\npart 1: {:?}\npart 2: {:?}",
+                    cards(hand),
+                    hand.bet,
+                    part1::decide_hand_type(&hand.cards),
+                    part2::decide_hand_type(&hand.cards)
+                ))
+            }
+            "ties" => {
+                if !args.is_empty() {
+                    return Err(QueryError::Usage("ties"));
+                }
+
+                let mut lines = HashMap::<String, Vec<usize>>::new();
+                for (index, hand) in input.0.iter().enumerate() {
+                    lines.entry(cards(hand)).or_default().push(index + 1);
+                }
+                let mut ties = lines
+                    .into_iter()
+                    .filter(|(_, lines)| lines.len() > 1)
+                    .collect::<Vec<_>>();
+                ties.sort_by_key(|(_, lines)| lines[0]);
+
+                if ties.is_empty() {
+                    return Ok(String::from("No ties"));
+                }
+                Ok(ties
+                    .iter()
+                    .map(|(cards, lines)| {
+                        let lines = lines.iter().map(|line| line.to_string());
+                        format!(
+                            "{} on lines {}",
+                            cards,
+                            lines.collect::<Vec<_>>().join(", ")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+// The hand the way it was written in the input.
+fn cards(hand: &Hand) -> String {
+    hand.cards
+        .iter()
+        .map(|card| match card {
+            Card::Ace => 'A',
+            Card::King => 'K',
+            Card::Queen => 'Q',
+            Card::Jack => 'J',
+            Card::Ten => 'T',
+            Card::Nine => '9',
+            Card::Eight => '8',
+            Card::Seven => '7',
+            Card::Six => '6',
+            Card::Five => '5',
+            Card::Four => '4',
+            Card::Three => '3',
+            Card::Two => '2',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query};
+
+    #[test]
+    fn explains_a_hand() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["2"]).unwrap(),
+            "T55J5 bets 684\npart 1: ThreeOfAKind\npart 2: FourOfAKind"
+        );
+    }
+
+    #[test]
+    fn finds_ties() {
+        let input = Puzzle::from_reader("KK677 28\n32T3K 765\nKK677 1\n".as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "ties", &[]).unwrap(),
+            "KK677 on lines 1, 3"
+        );
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(Queries::query(&input, "ties", &[]).unwrap(), "No ties");
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
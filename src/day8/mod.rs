@@ -1,11 +1,16 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{Error as IoError, Read};
+use std::io::{BufRead, Error as IoError};
+
+pub const TITLE: &str = "Haunted Wasteland";
 
 #[derive(Debug, PartialEq)]
 pub struct Puzzle {
@@ -14,74 +19,125 @@ pub struct Puzzle {
 }
 
 #[derive(Debug, PartialEq)]
-enum LR {
+pub enum LR {
     Left,
     Right,
 }
 
 #[derive(Debug, PartialEq)]
-struct Node {
+pub struct Node {
     left: String,
     right: String,
 }
 
+impl Puzzle {
+    pub fn path(&self) -> &[LR] {
+        &self.path
+    }
+
+    pub fn nodes(&self) -> &HashMap<String, Node> {
+        &self.nodes
+    }
+}
+
+impl Node {
+    pub fn left(&self) -> &str {
+        &self.left
+    }
+
+    pub fn right(&self) -> &str {
+        &self.right
+    }
+}
+
 #[derive(Debug)]
 pub enum Day8Error {
     Input,
     Direction(char),
     Node(String),
-    Paths(String),
+    Paths(Token),
     MissingNode(String),
+    // Walking from this node goes round in a loop without ever getting to the end.
+    NeverArrives(String),
+    TooLarge,
     IoError(IoError),
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = Day8Error;
-
-    fn try_from(mut value: File) -> Result<Self, Self::Error> {
-        let mut buf = String::new();
-        value.read_to_string(&mut buf)?;
-        (&buf[..]).try_into()
-    }
-}
-
-impl TryFrom<&str> for Puzzle {
-    type Error = Day8Error;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day8Error>;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (path, nodes) = value.trim().split_once("\n\n").ok_or(Day8Error::Input)?;
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
+        let mut lines = input::lines(reader);
 
-        let path = path
-            .trim()
-            .chars()
-            .map(|char| char.try_into())
+        let (index, line) = lines.next().ok_or(Day8Error::Input)?;
+        let line = line.map_err(Day8Error::from)?;
+        let directions = line.trim();
+        let path = directions
+            .char_indices()
+            .map(|(start, char)| {
+                LR::try_from(char).map_err(|err| {
+                    let token = Token::new(&line, &directions[start..start + char.len_utf8()]);
+                    Located::new(err, Location::new(index, &line, Some(&token)))
+                })
+            })
             .collect::<Result<Vec<LR>, _>>()?;
+        // Walking an empty path would never get anywhere.
+        if path.is_empty() {
+            return Err(Located::new(
+                Day8Error::Input,
+                Location::new(index, &line, None),
+            ));
+        }
 
-        let nodes = nodes
-            .split('\n')
-            .map(|line| {
-                let (name, directions) = line
-                    .split_once(" = ")
-                    .ok_or(Day8Error::Node(String::from(line)))?;
+        // The path and the nodes are separated by a blank line.
+        let (index, line) = lines.next().ok_or(Day8Error::Input)?;
+        let line = line.map_err(Day8Error::from)?;
+        if !line.trim().is_empty() {
+            return Err(Located::new(
+                Day8Error::Input,
+                Location::new(index, &line, None),
+            ));
+        }
 
-                let (left, right) = directions
-                    .split_once(", ")
-                    .ok_or(Day8Error::Paths(String::from(directions)))?;
+        let mut nodes = HashMap::new();
+        for (index, line) in lines {
+            let line = line.map_err(Day8Error::from)?;
+            if line.trim().is_empty() {
+                continue;
+            }
 
-                Ok((
-                    String::from(name),
-                    Node {
-                        left: String::from(&left[1..]),
-                        right: String::from(&right[..right.len() - 1]),
-                    },
-                ))
-            })
-            .collect::<Result<HashMap<_, _>, Day8Error>>()?;
+            let (name, node) = parse_node(&line).map_err(|err| {
+                let location = Location::new(index, &line, err.token());
+                Located::new(err, location)
+            })?;
+            nodes.insert(name, node);
+        }
 
         Ok(Self { path, nodes })
     }
 }
 
+// This is parsing an "AAA = (BBB, CCC)" line.
+fn parse_node(line: &str) -> Result<(String, Node), Day8Error> {
+    let (name, directions) = line
+        .split_once(" = ")
+        .ok_or(Day8Error::Node(String::from(line)))?;
+
+    let (left, right) = directions
+        .strip_prefix('(')
+        .and_then(|directions| directions.strip_suffix(')'))
+        .and_then(|directions| directions.split_once(", "))
+        .ok_or_else(|| Day8Error::Paths(Token::new(line, directions)))?;
+
+    Ok((
+        String::from(name),
+        Node {
+            left: String::from(left),
+            right: String::from(right),
+        },
+    ))
+}
+
 impl TryFrom<char> for LR {
     type Error = Day8Error;
 
@@ -94,6 +150,15 @@ impl TryFrom<char> for LR {
     }
 }
 
+impl Day8Error {
+    fn token(&self) -> Option<&Token> {
+        match self {
+            Self::Paths(directions) => Some(directions),
+            _ => None,
+        }
+    }
+}
+
 impl Display for Day8Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -102,12 +167,32 @@ impl Display for Day8Error {
             Self::Node(string) => write!(f, "Bad node '{}'", string),
             Self::Paths(string) => write!(f, "Bad paths '{}'", string),
             Self::MissingNode(string) => write!(f, "Couldn't find node with name '{}'", string),
-            Self::IoError(err) => err.fmt(f),
+            Self::NeverArrives(start) => write!(f, "Walking from '{}' never gets anywhere", start),
+            Self::TooLarge => write!(f, "Too many steps to fit in a number"),
+            Self::IoError(_) => write!(f, "I/O error"),
         }
     }
 }
 
-impl Error for Day8Error {}
+impl Error for Day8Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl PuzzleError for Day8Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::MissingNode(_) | Self::NeverArrives(_) => Category::InvalidPuzzle,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
+    }
+}
 
 impl From<IoError> for Day8Error {
     fn from(value: IoError) -> Self {
@@ -117,7 +202,12 @@ impl From<IoError> for Day8Error {
 
 #[cfg(test)]
 mod tests {
-    use super::{HashMap, Node, Puzzle, LR};
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{Day8Error, HashMap, Node, Puzzle, LR};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
 
     #[test]
     fn example_1() {
@@ -176,7 +266,7 @@ mod tests {
                 ),
             ]),
         };
-        let result: Puzzle = input.try_into().unwrap();
+        let result = Puzzle::from_reader(input.as_bytes()).unwrap();
 
         assert_eq!(result, expected);
     }
@@ -210,8 +300,33 @@ mod tests {
                 ),
             ]),
         };
-        let result: Puzzle = input.try_into().unwrap();
+        let result = Puzzle::from_reader(input.as_bytes()).unwrap();
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn points_at_bad_node() {
+        let input = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD EEE)\n";
+        let err = Puzzle::from_reader(input.as_bytes()).unwrap_err();
+        let location = err.location.unwrap();
+        assert_eq!((location.line, location.column, location.width), (4, 7, 9));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+            |input| {
+                let _ = Puzzle::from_reader(input).map(Part1::solve);
+                let _ = Puzzle::from_reader(input).map(Part2::solve);
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_unbracketed_paths() {
+        let err = Puzzle::from_reader("L\n\nAAA = BBB, CCC)\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.error, Day8Error::Paths(_)));
+    }
 }
@@ -1,10 +1,48 @@
-use super::{Day8Error, Puzzle, LR};
+use super::{Day8Error, Puzzle, LR, TITLE};
+use crate::solution::{Example, Solution};
+use std::collections::HashSet;
+
+const EXAMPLE_1: &str = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)\n";
+const EXAMPLE_2: &str = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: usize = 8;
+    const PART: usize = 1;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE_1,
+            answer: "2",
+        },
+        Example {
+            input: EXAMPLE_2,
+            answer: "6",
+        },
+    ];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day8Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        count_steps(input)
+    }
+}
 
 pub fn count_steps(puzzle: Puzzle) -> Result<usize, Day8Error> {
     let mut current_location = "AAA";
     let mut steps = 0;
+    // Being at the same node at the same point in the path twice means it's going round in
+    // circles.
+    let mut visited = HashSet::<(&str, usize)>::new();
 
     while current_location != "ZZZ" {
+        if !visited.insert((current_location, steps % puzzle.path.len())) {
+            return Err(Day8Error::NeverArrives(String::from("AAA")));
+        }
+
         let node = puzzle
             .nodes
             .get(current_location)
@@ -24,21 +62,30 @@ pub fn count_steps(puzzle: Puzzle) -> Result<usize, Day8Error> {
 
 #[cfg(test)]
 mod tests {
-    use super::{count_steps, Puzzle};
+    use super::{count_steps, Day8Error, Puzzle, EXAMPLE_1, EXAMPLE_2};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn example_1() {
-        let input: Puzzle = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)\n".try_into().unwrap();
+        let input = Puzzle::from_reader(EXAMPLE_1.as_bytes()).unwrap();
         let steps = count_steps(input).unwrap();
         assert_eq!(steps, 2);
     }
 
     #[test]
     fn example_2() {
-        let input: Puzzle = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"
-            .try_into()
-            .unwrap();
+        let input = Puzzle::from_reader(EXAMPLE_2.as_bytes()).unwrap();
         let steps = count_steps(input).unwrap();
         assert_eq!(steps, 6);
     }
+
+    #[test]
+    fn notices_going_in_circles() {
+        let input = "LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = Puzzle::from_reader(input.as_bytes()).unwrap();
+        assert!(matches!(
+            count_steps(input),
+            Err(Day8Error::NeverArrives(start)) if start == "AAA"
+        ));
+    }
 }
@@ -1,6 +1,29 @@
 use std::collections::HashMap;
 
-use super::{Day8Error, Puzzle, LR};
+use super::{Day8Error, Puzzle, LR, TITLE};
+use crate::solution::{Example, Solution};
+
+const EXAMPLE: &str = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\n";
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 8;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "6",
+    }];
+
+    type Input = Puzzle;
+    type Output = usize;
+    type Error = Day8Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        count_ghost_steps(input)
+    }
+}
 
 pub fn count_ghost_steps(puzzle: Puzzle) -> Result<usize, Day8Error> {
     let paths = puzzle
@@ -19,7 +42,10 @@ pub fn count_ghost_steps(puzzle: Puzzle) -> Result<usize, Day8Error> {
         })
         .collect::<Result<Vec<_>, Day8Error>>()?;
 
-    Ok(paths.iter().fold(1, |curr, path_len| lcm(curr, *path_len)))
+    paths
+        .iter()
+        .try_fold(1, |curr, path_len| lcm(curr, *path_len))
+        .ok_or(Day8Error::TooLarge)
 }
 
 fn count_steps_from(puzzle: &Puzzle, start: &str) -> Result<usize, Day8Error> {
@@ -35,8 +61,11 @@ fn count_steps_from(puzzle: &Puzzle, start: &str) -> Result<usize, Day8Error> {
             break;
         }
 
-        // Save it and take a step.
-        tracked_locations.insert((current_location, steps % puzzle.path.len()), steps);
+        // Save it and take a step. If we've been here before, we're going round in circles.
+        let key = (current_location, steps % puzzle.path.len());
+        if tracked_locations.insert(key, steps).is_some() {
+            return Err(Day8Error::NeverArrives(String::from(start)));
+        }
 
         let node = puzzle
             .nodes
@@ -59,8 +88,9 @@ fn is_end_node(location: &str) -> bool {
     location.ends_with('Z')
 }
 
-fn lcm(a: usize, b: usize) -> usize {
-    a / gcd(a, b) * b
+// None if it doesn't fit in a usize.
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    (a / gcd(a, b)).checked_mul(b)
 }
 
 fn gcd(a: usize, b: usize) -> usize {
@@ -73,11 +103,12 @@ fn gcd(a: usize, b: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::{count_ghost_steps, Puzzle};
+    use super::{count_ghost_steps, Puzzle, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn example() {
-        let puzzle: Puzzle = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\n".try_into().unwrap();
+        let puzzle = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let steps = count_ghost_steps(puzzle).unwrap();
         assert_eq!(steps, 6);
     }
@@ -0,0 +1,117 @@
+use super::{Puzzle, LR};
+use crate::query::{self, Command, Query, QueryError};
+use std::collections::HashMap;
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 8;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "step",
+        usage: "step N",
+        help: "Show which node part 1 is on after N steps from AAA",
+    }];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "step" => {
+                let steps = query::argument::<usize>(args, "step N")?;
+                let location = walk(input, steps)?;
+
+                let next = match input.path[steps % input.path.len()] {
+                    LR::Left => "left",
+                    LR::Right => "right",
+                };
+                Ok(format!(
+                    "Step {}: at {}, going {} next",
+                    steps, location, next
+                ))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+// Where part 1 is after some number of steps from AAA. The walk has to start repeating itself
+// sooner or later, and once it does the rest of the steps just go round the loop, so huge step
+// counts don't take forever.
+fn walk(input: &Puzzle, steps: usize) -> Result<&str, QueryError> {
+    let mut seen = HashMap::<(&str, usize), usize>::new();
+    let mut location = "AAA";
+    let mut end = steps;
+    let mut step = 0;
+    while step < end {
+        let instruction = step % input.path.len();
+        if let Some(first) = seen.insert((location, instruction), step) {
+            end = step + (end - step) % (step - first);
+            if step == end {
+                break;
+            }
+        }
+
+        let node = input
+            .nodes
+            .get(location)
+            .ok_or(QueryError::NotFound(format!("node {}", location)))?;
+        location = match input.path[instruction] {
+            LR::Left => &node.left,
+            LR::Right => &node.right,
+        };
+        step += 1;
+    }
+    Ok(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query, QueryError};
+
+    #[test]
+    fn takes_steps() {
+        let input = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = Puzzle::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "step", &["0"]).unwrap(),
+            "Step 0: at AAA, going left next"
+        );
+        assert_eq!(
+            Queries::query(&input, "step", &["3"]).unwrap(),
+            "Step 3: at BBB, going left next"
+        );
+        assert_eq!(
+            Queries::query(&input, "walk", &[]),
+            Err(QueryError::UnknownCommand(String::from("walk")))
+        );
+    }
+
+    #[test]
+    fn skips_round_loops() {
+        let input = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = Puzzle::from_reader(input.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "step", &["1000000000000"]).unwrap(),
+            "Step 1000000000000: at ZZZ, going left next"
+        );
+        assert_eq!(
+            Queries::query(&input, "step", &[&usize::MAX.to_string()]).unwrap(),
+            "Step 18446744073709551615: at ZZZ, going left next"
+        );
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+            |input| {
+                if let Ok(input) = Puzzle::from_reader(input) {
+                    query::ask_everything::<Queries>(&input);
+                }
+            },
+        );
+    }
+}
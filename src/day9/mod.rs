@@ -1,49 +1,52 @@
 pub mod part1;
 pub mod part2;
+pub mod query;
 
+use crate::diagnostic::{Located, Location, Token};
+use crate::error::{Category, PuzzleError};
+use crate::input::{self, PuzzleInput};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error as IoError};
+use std::io::{BufRead, Error as IoError};
 use std::num::ParseIntError;
 
+pub const TITLE: &str = "Mirage Maintenance";
+
+pub const EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
 #[derive(Debug, PartialEq)]
 pub struct Puzzle(Vec<Vec<isize>>);
 
+impl Puzzle {
+    pub fn histories(&self) -> &[Vec<isize>] {
+        &self.0
+    }
+}
+
 #[derive(Debug)]
 pub enum Day9Error {
-    ParseIntError(ParseIntError),
+    ParseIntError(String, ParseIntError),
+    TooLarge,
     IoError(IoError),
 }
 
-impl TryFrom<File> for Puzzle {
-    type Error = Day9Error;
-
-    fn try_from(value: File) -> Result<Self, Self::Error> {
-        let buf = BufReader::new(value);
-        Ok(Puzzle(
-            buf.lines()
-                .map(|line| {
-                    line?
-                        .split_whitespace()
-                        .map(|num| num.parse::<isize>().map_err(Day9Error::ParseIntError))
-                        .collect::<Result<_, _>>()
-                })
-                .collect::<Result<_, _>>()?,
-        ))
-    }
-}
-
-impl TryFrom<&str> for Puzzle {
-    type Error = Day9Error;
+impl PuzzleInput for Puzzle {
+    type Error = Located<Day9Error>;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error> {
         Ok(Puzzle(
-            value
-                .lines()
-                .map(|line| {
+            input::records(reader)
+                .map(|(index, line)| {
+                    let line = line.map_err(Day9Error::IoError)?;
                     line.split_whitespace()
-                        .map(|num| num.parse::<isize>().map_err(Day9Error::ParseIntError))
+                        .map(|num| {
+                            num.parse::<isize>().map_err(|err| {
+                                Located::new(
+                                    Day9Error::ParseIntError(String::from(num), err),
+                                    Location::new(index, &line, Some(&Token::new(&line, num))),
+                                )
+                            })
+                        })
                         .collect::<Result<_, _>>()
                 })
                 .collect::<Result<_, _>>()?,
@@ -54,13 +57,32 @@ impl TryFrom<&str> for Puzzle {
 impl Display for Day9Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::ParseIntError(err) => err.fmt(f),
-            Self::IoError(err) => err.fmt(f),
+            Self::ParseIntError(num, _) => write!(f, "Bad number '{}'", num),
+            Self::TooLarge => write!(f, "Numbers got too large to extrapolate"),
+            Self::IoError(_) => write!(f, "I/O error"),
+        }
+    }
+}
+
+impl Error for Day9Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            Self::ParseIntError(_, err) => Some(err),
+            Self::TooLarge => None,
         }
     }
 }
 
-impl Error for Day9Error {}
+impl PuzzleError for Day9Error {
+    fn category(&self) -> Category {
+        match self {
+            Self::IoError(_) => Category::Io,
+            Self::TooLarge => Category::Unsolvable,
+            _ => Category::Parse,
+        }
+    }
+}
 
 impl From<IoError> for Day9Error {
     fn from(value: IoError) -> Self {
@@ -70,18 +92,47 @@ impl From<IoError> for Day9Error {
 
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::part1::Part1;
+    use super::part2::Part2;
+    use super::{Day9Error, Puzzle, EXAMPLE};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::solution::Solution;
 
     #[test]
     fn example() {
-        let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+        let input = EXAMPLE;
         let expected = Puzzle(vec![
             vec![0, 3, 6, 9, 12, 15],
             vec![1, 3, 6, 10, 15, 21],
             vec![10, 13, 16, 21, 30, 45],
         ]);
 
-        let result: Puzzle = input.try_into().unwrap();
+        let result = Puzzle::from_reader(input.as_bytes()).unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn points_at_the_bad_number() {
+        // The first '-' is part of a perfectly good number.
+        let err = Puzzle::from_reader("1 -1 -\n".as_bytes()).unwrap_err();
+        let location = err.location.unwrap();
+        assert_eq!((location.column, location.width), (6, 1));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            let _ = Puzzle::from_reader(input).map(Part1::solve);
+            let _ = Puzzle::from_reader(input).map(Part2::solve);
+        });
+    }
+
+    #[test]
+    fn errors_instead_of_overflowing() {
+        let input = || Puzzle::from_reader("0 9223372036854775807\n".as_bytes()).unwrap();
+        assert!(matches!(Part1::solve(input()), Err(Day9Error::TooLarge)));
+        let input = || Puzzle::from_reader("-1 9223372036854775807\n".as_bytes()).unwrap();
+        assert!(matches!(Part2::solve(input()), Err(Day9Error::TooLarge)));
+    }
 }
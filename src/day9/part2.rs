@@ -1,29 +1,55 @@
-use super::{Day9Error, Puzzle};
+use super::{Day9Error, Puzzle, EXAMPLE, TITLE};
+use crate::solution::{Example, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: usize = 9;
+    const PART: usize = 2;
+    const TITLE: &'static str = TITLE;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answer: "2",
+    }];
+
+    type Input = Puzzle;
+    type Output = isize;
+    type Error = Day9Error;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+        sum_extensions(input)
+    }
+}
 
 pub fn sum_extensions(puzzle: Puzzle) -> Result<isize, Day9Error> {
-    Ok(puzzle.0.into_iter().map(|list| get_extension(&list)).sum())
+    puzzle.0.iter().try_fold(0_isize, |sum, list| {
+        get_extension(list)
+            .and_then(|extension| sum.checked_add(extension))
+            .ok_or(Day9Error::TooLarge)
+    })
 }
 
-fn get_extension(nums: &[isize]) -> isize {
-    let next = if nums.iter().all(|num| *num == 0) {
-        0
-    } else {
-        let differences: Vec<isize> = nums.windows(2).map(|w| w[1] - w[0]).collect();
-        nums.first().unwrap() - get_extension(&differences)
-    };
+// None if any of the differences (or the extension itself) don't fit in an isize.
+fn get_extension(nums: &[isize]) -> Option<isize> {
+    if nums.iter().all(|num| *num == 0) {
+        return Some(0);
+    }
 
-    next
+    let differences = nums
+        .windows(2)
+        .map(|w| w[1].checked_sub(w[0]))
+        .collect::<Option<Vec<_>>>()?;
+    nums.first()?.checked_sub(get_extension(&differences)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{sum_extensions, Puzzle};
+    use super::{sum_extensions, Puzzle, EXAMPLE};
+    use crate::input::PuzzleInput;
 
     #[test]
     fn example() {
-        let input: Puzzle = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n"
-            .try_into()
-            .unwrap();
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
         let result = sum_extensions(input).unwrap();
         assert_eq!(result, 2);
     }
@@ -0,0 +1,112 @@
+use super::Puzzle;
+use crate::query::{self, Command, Query, QueryError};
+
+pub struct Queries;
+
+impl Query for Queries {
+    const DAY: usize = 9;
+    const COMMANDS: &'static [Command] = &[Command {
+        name: "explain",
+        usage: "explain LINE",
+        help: "Show a history's differences, extended backwards and forwards",
+    }];
+
+    type Input = Puzzle;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        match command {
+            "explain" => {
+                let line = query::argument::<usize>(args, "explain LINE")?;
+                let history = line
+                    .checked_sub(1)
+                    .and_then(|index| input.0.get(index))
+                    .ok_or(QueryError::NotFound(format!("history on line {}", line)))?;
+
+                // Keep taking differences until they're all zero, like the puzzle shows. The
+                // solvers give up on histories that overflow, and so does this.
+                let mut rows = vec![history.clone()];
+                while let Some(row) = rows.last().filter(|row| row.iter().any(|num| *num != 0)) {
+                    let differences = row
+                        .windows(2)
+                        .map(|pair| pair[1].checked_sub(pair[0]))
+                        .collect::<Option<_>>()
+                        .ok_or(QueryError::TooLarge)?;
+                    rows.push(differences);
+                }
+
+                // Then fill in each end from the bottom up.
+                let mut before = 0_isize;
+                let mut after = 0_isize;
+                let mut ends = vec![];
+                for row in rows.iter().rev() {
+                    before = row
+                        .first()
+                        .unwrap_or(&0)
+                        .checked_sub(before)
+                        .ok_or(QueryError::TooLarge)?;
+                    after = after
+                        .checked_add(*row.last().unwrap_or(&0))
+                        .ok_or(QueryError::TooLarge)?;
+                    ends.push((before, after));
+                }
+                ends.reverse();
+
+                Ok(rows
+                    .iter()
+                    .zip(ends)
+                    .enumerate()
+                    .map(|(depth, (row, (before, after)))| {
+                        let row = row.iter().map(|num| num.to_string()).collect::<Vec<_>>();
+                        format!(
+                            "{}({}) {} ({})",
+                            " ".repeat(depth),
+                            before,
+                            row.join(" "),
+                            after
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(QueryError::UnknownCommand(String::from(command))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EXAMPLE;
+    use super::{Puzzle, Queries};
+    use crate::fuzz;
+    use crate::input::PuzzleInput;
+    use crate::query::{self, Query, QueryError};
+
+    #[test]
+    fn explains_a_history() {
+        let input = Puzzle::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            Queries::query(&input, "explain", &["3"]).unwrap(),
+            "(5) 10 13 16 21 30 45 (68)\n (5) 3 3 5 9 15 (23)\n  (-2) 0 2 4 6 (8)\n   (2) 2 2 2 (2)\n    (0) 0 0 (0)"
+        );
+    }
+
+    #[test]
+    fn errors_instead_of_overflowing() {
+        for history in ["-1 9223372036854775807\n", "0 9223372036854775807\n"] {
+            let input = Puzzle::from_reader(history.as_bytes()).unwrap();
+            assert_eq!(
+                Queries::query(&input, "explain", &["1"]),
+                Err(QueryError::TooLarge)
+            );
+        }
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        fuzz::never_panics(EXAMPLE, |input| {
+            if let Ok(input) = Puzzle::from_reader(input) {
+                query::ask_everything::<Queries>(&input);
+            }
+        });
+    }
+}
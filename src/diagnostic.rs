@@ -0,0 +1,177 @@
+use crate::error::{Category, PuzzleError};
+use std::error::Error;
+use std::fmt::Display;
+
+// Where in the input a parse error happened, along with the line itself so it can be shown
+// without going back to the file. Lines and columns count from 1, like an editor does.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub width: usize,
+    pub source: String,
+}
+
+// The bit of a line that an error is about, and where it starts in that line in bytes. Looking
+// the text up in the line instead would point at the wrong place if it shows up more than once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub text: String,
+    pub start: usize,
+}
+
+// A parse error and where it happened. Some errors can't point anywhere (the input ran out
+// early, or the file couldn't be read), so the location is optional.
+#[derive(Debug)]
+pub struct Located<E> {
+    pub error: E,
+    pub location: Option<Location>,
+}
+
+impl Location {
+    // Points at `token` in the line at `index` (counting from 0, like `enumerate` does). With no
+    // token, or one that isn't in the line, the whole line is the problem.
+    pub fn new(index: usize, source: &str, token: Option<&Token>) -> Self {
+        let before = token.and_then(|token| source.get(..token.start));
+        let (column, width) = match (token, before) {
+            (Some(token), Some(before)) if !token.text.is_empty() => {
+                (before.chars().count() + 1, token.text.chars().count())
+            }
+            _ => (1, source.chars().count()),
+        };
+
+        Location {
+            line: index + 1,
+            column,
+            width: width.max(1),
+            source: String::from(source),
+        }
+    }
+
+    // Shows the line with carets under the bad part, the way rustc does:
+    //
+    //   --> inputs/day5.txt:12:7
+    //    |
+    // 12 | 50 98 x
+    //    |       ^
+    pub fn render(&self, path: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "{gutter}--> {path}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self.line,
+            self.column,
+            self.line,
+            self.source,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width),
+        )
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+impl Token {
+    // `text` has to be a slice of `line` itself, not just the same characters, since where it is
+    // in memory is how we know where it is in the line.
+    pub fn new(line: &str, text: &str) -> Self {
+        Token {
+            text: String::from(text),
+            start: text.as_ptr() as usize - line.as_ptr() as usize,
+        }
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl<E> Located<E> {
+    pub fn new(error: E, location: Location) -> Self {
+        Located {
+            error,
+            location: Some(location),
+        }
+    }
+}
+
+// Anything that goes through `?` without a location just doesn't get one.
+impl<E> From<E> for Located<E> {
+    fn from(value: E) -> Self {
+        Located {
+            error: value,
+            location: None,
+        }
+    }
+}
+
+impl<E: Display> Display for Located<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+// Displays the same as the error it wraps, so skip straight to whatever caused that.
+impl<E: Error> Error for Located<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: PuzzleError> PuzzleError for Located<E> {
+    fn category(&self) -> Category {
+        self.error.category()
+    }
+
+    fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Location, Token};
+
+    #[test]
+    fn points_at_the_token() {
+        let line = "50 98 x";
+        let location = Location::new(11, line, Some(&Token::new(line, &line[6..])));
+        assert_eq!(location.line, 12);
+        assert_eq!(location.column, 7);
+        assert_eq!(location.width, 1);
+    }
+
+    #[test]
+    fn points_at_the_right_copy() {
+        let line = "1 -1 -";
+        let location = Location::new(0, line, Some(&Token::new(line, &line[5..])));
+        assert_eq!((location.column, location.width), (6, 1));
+    }
+
+    #[test]
+    fn falls_back_to_the_whole_line() {
+        let nope = Token {
+            text: String::from("nope"),
+            start: 20,
+        };
+        let location = Location::new(0, "Game one", Some(&nope));
+        assert_eq!((location.column, location.width), (1, 8));
+        let location = Location::new(0, "", None);
+        assert_eq!((location.column, location.width), (1, 1));
+    }
+
+    #[test]
+    fn renders_like_a_compiler() {
+        let line = "50 98 x";
+        let location = Location::new(11, line, Some(&Token::new(line, &line[6..])));
+        assert_eq!(
+            location.render("inputs/day5.txt"),
+            "  --> inputs/day5.txt:12:7\n   |\n12 | 50 98 x\n   |       ^"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::diagnostic::Location;
+use std::error::Error;
+use std::fmt::Display;
+
+// What kind of thing went wrong, so the runner can decide what to do about it without knowing
+// which day the error came from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Category {
+    // Reading the input fell over partway through.
+    Io,
+    // The input isn't in the shape the puzzle describes.
+    Parse,
+    // The input parsed fine but doesn't make sense, like a node that's never defined.
+    InvalidPuzzle,
+    // The puzzle makes sense, but the solver couldn't come up with an answer.
+    Unsolvable,
+}
+
+// Every day's error implements this, which is all the runner needs to know about it. The
+// underlying `ParseIntError` or `io::Error` (if there is one) is its `source()`.
+pub trait PuzzleError: Error + Send + Sync + 'static {
+    fn category(&self) -> Category;
+
+    fn location(&self) -> Option<&Location> {
+        None
+    }
+}
+
+// What a solver that panicked gets reported as, since there's no error to hand back.
+#[derive(Debug)]
+pub struct Panicked;
+
+impl Display for Panicked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Panicked while solving")
+    }
+}
+
+impl Error for Panicked {}
+
+impl PuzzleError for Panicked {
+    fn category(&self) -> Category {
+        Category::Unsolvable
+    }
+}
+
+// An error followed by everything that caused it, like "Bad number 'x': invalid digit found in
+// string".
+pub fn chain(err: &dyn Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chain, Display, Error};
+
+    #[derive(Debug)]
+    struct Outer(std::num::ParseIntError);
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Bad number")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn follows_the_source_chain() {
+        let err = Outer("x".parse::<usize>().unwrap_err());
+        assert_eq!(chain(&err), "Bad number: invalid digit found in string");
+    }
+}
@@ -0,0 +1,80 @@
+// A small randomized tester for the parsers. No crates here, so instead of a proper fuzzer it
+// takes a day's example and mangles it a few thousand ways, plus some pure garbage, and makes
+// sure the parser never panics on any of it. Errors are fine, that's the whole point.
+
+use std::panic::{self, AssertUnwindSafe};
+
+const ROUNDS: usize = 2000;
+
+// Bytes that tend to confuse parsers: separators the puzzles use, digits that can make numbers
+// overflow, and a byte that's never valid UTF-8.
+const INTERESTING: &[u8] = b"0123456789 :;,|()=-+\n\t\xff";
+
+// xorshift64, which is plenty random for this and means a failure is reproducible. Tests that
+// want random lines of their own can borrow it too.
+pub struct Rng(pub u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound.max(1) as u64) as usize
+    }
+
+    fn byte(&mut self) -> u8 {
+        if self.next().is_multiple_of(2) {
+            INTERESTING[self.below(INTERESTING.len())]
+        } else {
+            self.next() as u8
+        }
+    }
+}
+
+// Makes a handful of random edits to `input`.
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>) {
+    for _ in 0..=rng.below(8) {
+        let at = rng.below(input.len() + 1);
+        match rng.below(5) {
+            0 if at < input.len() => input[at] = rng.byte(),
+            1 => input.insert(at, rng.byte()),
+            2 if at < input.len() => {
+                input.remove(at);
+            }
+            3 => {
+                let end = (at + rng.below(16)).min(input.len());
+                let copy = input[at..end].to_vec();
+                input.splice(at..at, copy);
+            }
+            4 => input.truncate(at),
+            _ => (),
+        }
+    }
+}
+
+// Throws mangled versions of `example` and some random bytes at `parse`, and fails with the input
+// that made it panic if any of them do.
+pub fn never_panics<F: Fn(&[u8])>(example: &str, parse: F) {
+    let mut rng = Rng(0x2023_1201_dead_beef);
+
+    for round in 0..ROUNDS {
+        let input = if round % 10 == 0 {
+            (0..rng.below(64)).map(|_| rng.byte()).collect()
+        } else {
+            let mut input = example.as_bytes().to_vec();
+            mutate(&mut rng, &mut input);
+            input
+        };
+
+        if panic::catch_unwind(AssertUnwindSafe(|| parse(&input))).is_err() {
+            panic!(
+                "Parser panicked on {:?}",
+                String::from_utf8_lossy(&input).into_owned()
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::{self, BufRead};
+use std::iter::Enumerate;
+
+// Anything a puzzle can be parsed from. Taking any `BufRead` instead of a `File` means the same
+// parser handles files, stdin and in-memory buffers.
+pub trait PuzzleInput: Sized {
+    type Error;
+
+    fn from_reader<R: BufRead>(reader: R) -> Result<Self, Self::Error>;
+}
+
+// The lines of an input with everything an editor might have added tidied away, so every parser
+// sees the same thing whether the file was saved on Windows or not:
+//
+// - A UTF-8 byte order mark at the start is dropped.
+// - Trailing whitespace, including the `\r` from `\r\n`, is trimmed off every line.
+// - Blank lines at the start and end are dropped, and a run of blank lines in the middle becomes
+//   one, since that's all the puzzles use them for.
+//
+// Each line comes with its index in the original input (counting from 0), so errors still point
+// at the right line even after some blank ones have been skipped.
+pub struct Lines<R> {
+    lines: Enumerate<io::Lines<R>>,
+    blank: Option<usize>,
+    next: Option<(usize, String)>,
+    started: bool,
+}
+
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        lines: reader.lines().enumerate(),
+        blank: None,
+        next: None,
+        started: false,
+    }
+}
+
+// For puzzles that are just one thing per line, where a blank line doesn't mean anything.
+pub fn records<R: BufRead>(reader: R) -> impl Iterator<Item = (usize, io::Result<String>)> {
+    lines(reader).filter(|(_, line)| !line.as_ref().is_ok_and(String::is_empty))
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = (usize, io::Result<String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((index, line)) = self.next.take() {
+            return Some((index, Ok(line)));
+        }
+
+        loop {
+            let (index, line) = self.lines.next()?;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some((index, Err(err))),
+            };
+
+            let line = if index == 0 {
+                line.strip_prefix('\u{feff}').unwrap_or(&line)
+            } else {
+                &line
+            };
+            let line = line.trim_end();
+
+            if line.is_empty() {
+                // Only worth remembering if something comes after it.
+                if self.started {
+                    self.blank.get_or_insert(index);
+                }
+                continue;
+            }
+
+            self.started = true;
+            let line = String::from(line);
+            return match self.blank.take() {
+                Some(blank) => {
+                    self.next = Some((index, line));
+                    Some((blank, Ok(String::new())))
+                }
+                None => Some((index, Ok(line))),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines, records};
+
+    fn normalize(input: &str) -> Vec<(usize, String)> {
+        lines(input.as_bytes())
+            .map(|(index, line)| (index, line.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn leaves_tidy_input_alone() {
+        assert_eq!(
+            normalize("a\n\nb\n"),
+            vec![(0, "a".into()), (1, "".into()), (2, "b".into())]
+        );
+    }
+
+    #[test]
+    fn strips_carriage_returns() {
+        assert_eq!(normalize("a\r\n\r\nb\r\n"), normalize("a\n\nb\n"));
+    }
+
+    #[test]
+    fn strips_byte_order_mark() {
+        assert_eq!(normalize("\u{feff}a\nb"), normalize("a\nb"));
+    }
+
+    #[test]
+    fn strips_trailing_whitespace() {
+        assert_eq!(normalize("a  \t\n \nb "), normalize("a\n\nb"));
+    }
+
+    #[test]
+    fn collapses_blank_lines() {
+        assert_eq!(
+            normalize("\n\na\n\n\n\nb\n\n\n"),
+            vec![(2, "a".into()), (3, "".into()), (6, "b".into())]
+        );
+    }
+
+    #[test]
+    fn records_skip_blank_lines() {
+        let records = records("a\n\n\nb\n".as_bytes())
+            .map(|(index, line)| (index, line.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(records, vec![(0, "a".into()), (3, "b".into())]);
+    }
+}
@@ -0,0 +1,22 @@
+// Every day's puzzle types and solvers, plus the plumbing for running them. The `aoc` binary is
+// just a command line on top of this, so anything it can do another tool can do too.
+
+pub mod cache;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod diagnostic;
+pub mod error;
+#[cfg(test)]
+mod fuzz;
+pub mod input;
+pub mod query;
+pub mod registry;
+pub mod runner;
+pub mod solution;
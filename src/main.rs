@@ -1,141 +1,290 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use std::error::Error;
-use std::fmt::Display;
+mod answers;
+mod bench;
+mod cli;
+mod repl;
+mod report;
+mod serve;
+mod summary;
+mod watch;
+
+use answers::{Answers, AnswersError, Verdict};
+use cli::{Args, CliError};
+use rs_advent_of_code_2023::cache::{self, Cache};
+use rs_advent_of_code_2023::registry;
+use rs_advent_of_code_2023::runner::{self, Input, Task};
+use std::env;
 use std::fs::File;
-use std::time::Instant;
+use std::process::{self, ExitCode};
+use summary::{Problem, Summary};
 
-fn main() {
-    run_puzzle(
-        "Day 1, Part 1",
-        day1::part1::sum_calibration_values,
-        "inputs/day1.txt",
-    );
+fn main() -> ExitCode {
+    let mut args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => exit_with_usage(err),
+    };
 
-    run_puzzle(
-        "Day 1, Part 2",
-        day1::part2::sum_calibration_values,
-        "inputs/day1.txt",
-    );
+    if args.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
 
-    run_puzzle(
-        "Day 2, Part 1",
-        day2::part1::sum_impossible_game_ids,
-        "inputs/day2.txt",
-    );
+    if let Err(err) = validate_selectors(&args) {
+        exit_with_usage(err);
+    }
 
-    run_puzzle(
-        "Day 2, Part 2",
-        day2::part2::sum_cube_powers,
-        "inputs/day2.txt",
-    );
+    if args.list {
+        for puzzle in registry::PUZZLES {
+            if args.includes(puzzle.day(), puzzle.part()) {
+                println!("{}: {}", puzzle.name(), puzzle.title());
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
 
-    run_puzzle(
-        "Day 3, Part 1",
-        day3::part1::sum_part_numbers,
-        "inputs/day3.txt",
-    );
+    // Like a failed save, this isn't worth failing over. Whatever's in the cache can't be
+    // trusted though, so everything gets solved fresh this time instead.
+    if args.clear_cache {
+        if let Err(err) = Cache::clear(cache::CACHE_PATH) {
+            eprintln!(
+                "Couldn't clear {}, so not using it: {}",
+                cache::CACHE_PATH,
+                err
+            );
+            args.no_cache = true;
+        }
+    }
 
-    run_puzzle(
-        "Day 3, Part 2",
-        day3::part2::sum_gear_ratios,
-        "inputs/day3.txt",
-    );
+    let summary = if args.verify {
+        verify_puzzles(&args)
+    } else if args.bench {
+        bench_puzzles(&args)
+    } else if args.example {
+        check_examples(&args)
+    } else if let Some(day) = args.repl_day().filter(|_| args.repl) {
+        let path = match args.inputs.first() {
+            Some(path) => path.clone(),
+            None => format!("inputs/day{}.txt", day),
+        };
+        repl::repl(day, &path, args.timeout)
+    } else if args.serve {
+        serve::serve(&args)
+    } else if args.watch {
+        watch::watch(&selected_tasks(&args), args.timeout)
+    } else {
+        run_puzzles(&args)
+    };
 
-    run_puzzle("Day 4, Part 1", day4::part1::sum_points, "inputs/day4.txt");
-    run_puzzle("Day 4, Part 2", day4::part2::count_cards, "inputs/day4.txt");
+    // Stdout might be JSON or CSV, so the summary goes somewhere it won't get in the way.
+    eprintln!("{}", summary);
+    summary.exit_code()
+}
 
-    run_puzzle(
-        "Day 5, Part 1",
-        day5::part1::get_lowest_location,
-        "inputs/day5.txt",
-    );
+fn exit_with_usage(err: CliError) -> ! {
+    eprintln!("{}\n\n{}", err, cli::USAGE);
+    process::exit(summary::USAGE_EXIT_CODE.into());
+}
 
-    run_puzzle(
-        "Day 5, Part 2",
-        day5::part2::get_lowest_range_location,
-        "inputs/day5.txt",
-    );
+// Every day that's asked for has to exist, so a typo doesn't quietly run nothing.
+fn validate_selectors(args: &Args) -> Result<(), CliError> {
+    for selector in args.selectors.iter() {
+        for day in selector.days.clone() {
+            if !registry::PUZZLES.iter().any(|puzzle| puzzle.day() == day) {
+                return Err(CliError::UnknownDay(day));
+            }
 
-    run_puzzle(
-        "Day 6, Part 1",
-        day6::part1::multiply_winning_ways,
-        "inputs/day6.txt",
-    );
+            if let Some(part) = selector.part {
+                if registry::find(day, part).is_none() {
+                    return Err(CliError::UnknownPart(day, part));
+                }
+            }
+        }
+    }
 
-    run_puzzle(
-        "Day 6, Part 2",
-        day6::part2::count_winning_ways,
-        "inputs/day6.txt",
-    );
+    Ok(())
+}
 
-    run_puzzle(
-        "Day 7, Part 1",
-        day7::part1::calculate_total_winnings,
-        "inputs/day7.txt",
-    );
+fn selected_tasks(args: &Args) -> Vec<Task> {
+    let inputs = match Input::expand(&args.inputs) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            eprintln!("Couldn't read inputs: {}", err);
+            process::exit(Problem::MissingInput.exit_code().into());
+        }
+    };
 
-    run_puzzle(
-        "Day 7, Part 2",
-        day7::part2::calculate_total_winnings,
-        "inputs/day7.txt",
-    );
+    let mut tasks = Vec::<Task>::new();
+    for puzzle in registry::PUZZLES {
+        if !args.includes(puzzle.day(), puzzle.part()) {
+            continue;
+        }
 
-    run_puzzle("Day 8, Part 1", day8::part1::count_steps, "inputs/day8.txt");
-    run_puzzle(
-        "Day 8, Part 2",
-        day8::part2::count_ghost_steps,
-        "inputs/day8.txt",
-    );
+        if args.example {
+            tasks.extend(Input::examples(*puzzle).into_iter().map(|input| Task {
+                puzzle: *puzzle,
+                input,
+            }));
+        } else if inputs.is_empty() {
+            tasks.push(Task {
+                puzzle: *puzzle,
+                input: Input::File(puzzle.input_path()),
+            });
+        } else {
+            tasks.extend(inputs.iter().map(|input| Task {
+                puzzle: *puzzle,
+                input: input.clone(),
+            }));
+        }
+    }
 
-    run_puzzle(
-        "Day 9, Part 1",
-        day9::part1::sum_extensions,
-        "inputs/day9.txt",
-    );
+    tasks
+}
+
+fn run_puzzles(args: &Args) -> Summary {
+    let mut summary = Summary::default();
+    let cache = load_cache(args);
+
+    if let Some(header) = args.format.header() {
+        println!("{}", header);
+    }
 
-    run_puzzle(
-        "Day 9, Part 2",
-        day9::part2::sum_extensions,
-        "inputs/day9.txt",
+    runner::run_all(
+        &selected_tasks(args),
+        args.jobs,
+        args.timeout,
+        cache.as_ref(),
+        |task, run| {
+            println!("{}", args.format.render(task, &run));
+            match &run.answer {
+                Ok(_) => summary.pass(),
+                Err(err) => summary.fail(task.name(), err.into()),
+            }
+        },
     );
+
+    // Losing the cache just means solving things again next time, so it's not worth failing over.
+    if let Some(Err(err)) = cache.map(|cache| cache.save()) {
+        eprintln!("Couldn't save {}: {}", cache::CACHE_PATH, err);
+    }
+
+    summary
 }
 
-fn run_puzzle<In: TryFrom<File>, Out: Display, Err: Error>(
-    puzzle_name: &str,
-    puzzle_fn: fn(input: In) -> Result<Out, Err>,
-    input_filename: &str,
-) where
-    In::Error: Error,
-{
-    let file = File::open(input_filename);
-    if let Err(err) = file {
-        println!("{}: Couldn't read file: {}", puzzle_name, err);
-        return;
+fn load_cache(args: &Args) -> Option<Cache> {
+    if args.no_cache {
+        return None;
     }
 
-    let start_time = Instant::now();
-    let input = file.unwrap().try_into();
-    if let Err(err) = input {
-        println!("{}: Couldn't parse input: {}", puzzle_name, err);
-        return;
+    Cache::load(cache::CACHE_PATH)
+        .inspect_err(|err| eprintln!("Couldn't load {}: {}", cache::CACHE_PATH, err))
+        .ok()
+}
+
+fn verify_puzzles(args: &Args) -> Summary {
+    let answers = File::open(answers::ANSWERS_PATH)
+        .map_err(AnswersError::from)
+        .and_then(Answers::try_from);
+    if let Err(err) = answers {
+        eprintln!("Couldn't load {}: {}", answers::ANSWERS_PATH, err);
+        process::exit(Problem::MissingInput.exit_code().into());
     }
 
-    let result = puzzle_fn(input.unwrap());
-    let elapsed_millis = start_time.elapsed().as_millis();
-    match result {
-        Ok(answer) => println!("{}: {} (in {} ms)", puzzle_name, answer, elapsed_millis),
-        Err(err) => println!(
-            "{}: Errored with {} (in {} ms, if you care)",
-            puzzle_name, err, elapsed_millis
-        ),
+    let answers = answers.unwrap();
+    let mut summary = Summary::default();
+
+    runner::run_all(
+        &selected_tasks(args),
+        args.jobs,
+        args.timeout,
+        None,
+        |task, run| {
+            let puzzle = task.puzzle;
+            let name = task.name();
+            let answer = match run.answer {
+                Ok(answer) => answer,
+                Err(err) => {
+                    println!("{}: FAIL ({})", name, err);
+                    summary.fail(name, (&err).into());
+                    return;
+                }
+            };
+
+            match answers.verify(task.input.name(), puzzle.day(), puzzle.part(), &answer) {
+                Verdict::Pass => {
+                    println!("{}: PASS ({})", name, answer);
+                    summary.pass();
+                }
+                Verdict::Fail(expected) => {
+                    println!("{}: FAIL (expected {}, got {})", name, expected, answer);
+                    summary.fail(name, Problem::Mismatch);
+                }
+                Verdict::Missing => {
+                    println!("{}: MISSING (got {})", name, answer);
+                    summary.pass();
+                }
+            }
+        },
+    );
+
+    summary
+}
+
+fn check_examples(args: &Args) -> Summary {
+    let mut summary = Summary::default();
+
+    runner::run_all(
+        &selected_tasks(args),
+        args.jobs,
+        args.timeout,
+        None,
+        |task, run| {
+            let name = task.name();
+            let Input::Example(_, example) = task.input else {
+                unreachable!("Only examples get checked against examples");
+            };
+
+            match run.answer {
+                Ok(answer) if answer == example.answer => {
+                    println!(
+                        "{}: PASS (expected {}, got {})",
+                        name, example.answer, answer
+                    );
+                    summary.pass();
+                }
+                Ok(answer) => {
+                    println!(
+                        "{}: FAIL (expected {}, got {})",
+                        name, example.answer, answer
+                    );
+                    summary.fail(name, Problem::Mismatch);
+                }
+                Err(err) => {
+                    println!("{}: FAIL (expected {}, {})", name, example.answer, err);
+                    summary.fail(name, (&err).into());
+                }
+            }
+        },
+    );
+
+    summary
+}
+
+fn bench_puzzles(args: &Args) -> Summary {
+    let mut summary = Summary::default();
+    println!("{}", bench::TABLE_HEADER);
+
+    for task in selected_tasks(args) {
+        let name = task.name();
+        match bench::bench(&task, args.iterations) {
+            Ok(bench) => {
+                println!("{}", bench::table_row(&name, &bench));
+                summary.pass();
+            }
+            Err(err) => {
+                eprintln!("{}: {}", name, err);
+                summary.fail(name, (&err).into());
+            }
+        }
     }
+
+    summary
 }
@@ -0,0 +1,138 @@
+use crate::error::PuzzleError;
+use crate::input::PuzzleInput;
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::io::BufRead;
+use std::str::FromStr;
+
+// A command a day understands in the REPL, on top of the ones every day gets.
+#[derive(Debug, PartialEq)]
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub help: &'static str,
+}
+
+// Ways to poke at a day's parsed input besides solving it, like which node day 8 is on after
+// 1000 steps. Each day implements this on a `Queries` marker, the same way the parts implement
+// `Solution`.
+pub trait Query {
+    const DAY: usize;
+    const COMMANDS: &'static [Command];
+
+    type Input: PuzzleInput + Debug;
+
+    fn query(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, QueryError>;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QueryError {
+    UnknownCommand(String),
+    Usage(&'static str),
+    NotFound(String),
+    TooLarge,
+}
+
+// The type-erased version of `Query`, so the REPL can hold any day's without knowing its input
+// type. Works just like `Puzzle` does for `Solution`.
+pub trait Inspector: Sync {
+    fn day(&self) -> usize;
+    fn commands(&self) -> &'static [Command];
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Box<dyn PuzzleError>>;
+    fn show(&self, input: &dyn Any) -> String;
+    fn query(&self, input: &dyn Any, command: &str, args: &[&str]) -> Result<String, QueryError>;
+}
+
+impl<Q> Inspector for Q
+where
+    Q: Query + Sync,
+    Q::Input: 'static,
+    <Q::Input as PuzzleInput>::Error: PuzzleError,
+{
+    fn day(&self) -> usize {
+        Q::DAY
+    }
+
+    fn commands(&self) -> &'static [Command] {
+        Q::COMMANDS
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Box<dyn PuzzleError>> {
+        let input =
+            Q::Input::from_reader(reader).map_err(|err| Box::new(err) as Box<dyn PuzzleError>)?;
+        Ok(Box::new(input))
+    }
+
+    fn show(&self, input: &dyn Any) -> String {
+        format!("{:#?}", downcast::<Q>(input))
+    }
+
+    fn query(&self, input: &dyn Any, command: &str, args: &[&str]) -> Result<String, QueryError> {
+        Q::query(downcast::<Q>(input), command, args)
+    }
+}
+
+fn downcast<Q: Query>(input: &dyn Any) -> &Q::Input
+where
+    Q::Input: 'static,
+{
+    input
+        .downcast_ref::<Q::Input>()
+        // Only ever handed what our own `parse` returned, so it's always the right type.
+        .expect("Input was parsed by a different day!")
+}
+
+// Most commands take a single number, like `step 1000`.
+pub fn argument<T: FromStr>(args: &[&str], usage: &'static str) -> Result<T, QueryError> {
+    match args {
+        [arg] => arg.parse().map_err(|_| QueryError::Usage(usage)),
+        _ => Err(QueryError::Usage(usage)),
+    }
+}
+
+// Asks every command a day has with a spread of arguments (none, small, huge), for fuzzing.
+#[cfg(test)]
+pub fn ask_everything<Q: Query>(input: &Q::Input) {
+    let max = usize::MAX.to_string();
+    let arguments: &[&[&str]] = &[&[], &["0"], &["1"], &["2"], &["3"], &["1000000"], &[&max]];
+    for command in Q::COMMANDS {
+        for args in arguments {
+            let _ = Q::query(input, command.name, args);
+        }
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => {
+                write!(f, "Unknown command '{}', try `help`", command)
+            }
+            Self::Usage(usage) => write!(f, "Usage: {}", usage),
+            Self::NotFound(what) => write!(f, "There's no {}", what),
+            Self::TooLarge => write!(f, "The numbers got too large to work that out"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{argument, QueryError};
+
+    #[test]
+    fn parses_one_argument() {
+        assert_eq!(argument::<usize>(&["42"], "step N"), Ok(42));
+        assert_eq!(
+            argument::<usize>(&["x"], "step N"),
+            Err(QueryError::Usage("step N"))
+        );
+        assert_eq!(
+            argument::<usize>(&["1", "2"], "step N"),
+            Err(QueryError::Usage("step N"))
+        );
+        assert!(argument::<usize>(&[], "step N").is_err());
+    }
+}
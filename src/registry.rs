@@ -0,0 +1,146 @@
+use crate::query::Inspector;
+use crate::solution::Puzzle;
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9};
+
+// Every solved puzzle, in the order they should run. Adding a day means adding its parts here.
+pub const PUZZLES: &[&dyn Puzzle] = &[
+    &day1::part1::Part1,
+    &day1::part2::Part2,
+    &day2::part1::Part1,
+    &day2::part2::Part2,
+    &day3::part1::Part1,
+    &day3::part2::Part2,
+    &day4::part1::Part1,
+    &day4::part2::Part2,
+    &day5::part1::Part1,
+    &day5::part2::Part2,
+    &day6::part1::Part1,
+    &day6::part2::Part2,
+    &day7::part1::Part1,
+    &day7::part2::Part2,
+    &day8::part1::Part1,
+    &day8::part2::Part2,
+    &day9::part1::Part1,
+    &day9::part2::Part2,
+];
+
+// Every day's REPL commands, in the same order.
+pub const INSPECTORS: &[&dyn Inspector] = &[
+    &day1::query::Queries,
+    &day2::query::Queries,
+    &day3::query::Queries,
+    &day4::query::Queries,
+    &day5::query::Queries,
+    &day6::query::Queries,
+    &day7::query::Queries,
+    &day8::query::Queries,
+    &day9::query::Queries,
+];
+
+pub fn find(day: usize, part: usize) -> Option<&'static dyn Puzzle> {
+    PUZZLES
+        .iter()
+        .find(|puzzle| puzzle.day() == day && puzzle.part() == part)
+        .copied()
+}
+
+pub fn inspector(day: usize) -> Option<&'static dyn Inspector> {
+    INSPECTORS
+        .iter()
+        .find(|inspector| inspector.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, inspector, PUZZLES};
+    use crate::runner::{self, Input, Task};
+
+    #[test]
+    fn registered_in_order_without_duplicates() {
+        let keys = PUZZLES
+            .iter()
+            .map(|puzzle| (puzzle.day(), puzzle.part()))
+            .collect::<Vec<_>>();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn finds_a_puzzle() {
+        let puzzle = find(5, 2).unwrap();
+        assert_eq!(puzzle.name(), "Day 5, Part 2");
+        assert_eq!(puzzle.input_path(), "inputs/day5.txt");
+        assert!(find(5, 3).is_none());
+    }
+
+    #[test]
+    fn every_day_can_be_inspected() {
+        for puzzle in PUZZLES {
+            let inspector = inspector(puzzle.day()).unwrap();
+            let input = inspector
+                .parse(&mut puzzle.examples()[0].input.as_bytes())
+                .unwrap();
+            assert!(!inspector.show(input.as_ref()).is_empty());
+        }
+    }
+
+    #[test]
+    fn every_example_gives_its_answer() {
+        for puzzle in PUZZLES {
+            assert!(
+                !puzzle.examples().is_empty(),
+                "{} has no examples",
+                puzzle.name()
+            );
+
+            for input in Input::examples(*puzzle) {
+                let Input::Example(_, example) = input else {
+                    unreachable!();
+                };
+                let task = Task {
+                    puzzle: *puzzle,
+                    input,
+                };
+                let answer = runner::run(&task).answer.unwrap();
+                assert_eq!(answer, example.answer, "{}", task.name());
+            }
+        }
+    }
+
+    type Variant = (&'static str, fn(&str) -> String);
+
+    // However the input was saved, the answer shouldn't change.
+    #[test]
+    fn examples_survive_messy_files() {
+        let variants: [Variant; 4] = [
+            ("CRLF", |input| input.replace('\n', "\r\n")),
+            ("BOM", |input| format!("\u{feff}{}", input)),
+            ("trailing spaces", |input| input.replace('\n', "  \n")),
+            ("extra blank lines", |input| {
+                format!("\n\n{}\n\n\n", input.replace("\n\n", "\n\n\n"))
+            }),
+        ];
+
+        for puzzle in PUZZLES {
+            for example in puzzle.examples() {
+                for (name, variant) in &variants {
+                    let task = Task {
+                        puzzle: *puzzle,
+                        input: Input::Stdin(variant(example.input).into_bytes().into()),
+                    };
+                    let answer = runner::run(&task).answer.map_err(|err| err.to_string());
+                    assert_eq!(
+                        answer.as_deref(),
+                        Ok(example.answer),
+                        "{} with {}",
+                        puzzle.name(),
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::summary::Summary;
+use rs_advent_of_code_2023::query::{Command, Inspector};
+use rs_advent_of_code_2023::registry;
+use rs_advent_of_code_2023::runner::{self, Failure, Input, Task};
+use std::any::Any;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+// The commands every day gets, whatever else it knows.
+const SHARED_COMMANDS: &[Command] = &[
+    Command {
+        name: "show",
+        usage: "show",
+        help: "Print everything that was parsed out of the input",
+    },
+    Command {
+        name: "solve",
+        usage: "solve PART",
+        help: "Solve a part against the input",
+    },
+    Command {
+        name: "help",
+        usage: "help",
+        help: "List the commands",
+    },
+    Command {
+        name: "quit",
+        usage: "quit",
+        help: "Leave (so does Ctrl-D)",
+    },
+];
+
+// One day's input, read and parsed once, waiting to be asked about.
+pub struct Session {
+    day: usize,
+    input: Arc<[u8]>,
+    inspector: &'static dyn Inspector,
+    parsed: Box<dyn Any>,
+    timeout: Option<Duration>,
+}
+
+impl Session {
+    // Every day with a solution has an inspector, which the command line has already checked.
+    pub fn load(day: usize, path: &str, timeout: Option<Duration>) -> Result<Self, Failure> {
+        let inspector = registry::inspector(day).expect("Every day can be inspected");
+        let input = Arc::<[u8]>::from(fs::read(path).map_err(Failure::Io)?);
+        let parsed = inspector.parse(&mut &input[..]).map_err(Failure::Parse)?;
+
+        Ok(Session {
+            day,
+            input,
+            inspector,
+            parsed,
+            timeout,
+        })
+    }
+
+    // What to print in answer to one line typed at the prompt, or `None` to stop.
+    pub fn respond(&self, line: &str) -> Option<String> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Some(String::new());
+        };
+        let args = words.collect::<Vec<_>>();
+
+        let response = match (command, &args[..]) {
+            ("quit" | "exit", _) => return None,
+            ("help", _) => self.help(),
+            ("show", []) => self.inspector.show(self.parsed.as_ref()),
+            ("solve", [part]) => self.solve(part),
+            ("show" | "solve", _) => format!("Usage: {}", usage(command)),
+            // A query that panics shouldn't take the whole session down with it, any more than a
+            // solve does.
+            _ => match panic::catch_unwind(AssertUnwindSafe(|| {
+                self.inspector.query(self.parsed.as_ref(), command, &args)
+            })) {
+                Ok(Ok(response)) => response,
+                Ok(Err(err)) => err.to_string(),
+                Err(_) => format!("Panicked while answering `{}`", line.trim()),
+            },
+        };
+        Some(response)
+    }
+
+    fn help(&self) -> String {
+        let commands = SHARED_COMMANDS.iter().chain(self.inspector.commands());
+        let width = commands
+            .clone()
+            .map(|command| command.usage.len())
+            .max()
+            .unwrap_or(0);
+        commands
+            .map(|command| format!("{:width$}  {}", command.usage, command.help))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Solving uses the input up, so each one parses its own copy. It goes through the runner
+    // like any other run, so a panic doesn't take the session down and `--timeout` still counts.
+    fn solve(&self, part: &str) -> String {
+        let Some(puzzle) = part
+            .parse()
+            .ok()
+            .and_then(|part| registry::find(self.day, part))
+        else {
+            return format!("There's no solution for day {}, part {}", self.day, part);
+        };
+
+        let task = Task {
+            puzzle,
+            input: Input::Stdin(self.input.clone()),
+        };
+        let run = match self.timeout {
+            Some(limit) => runner::run_with_timeout(&task, limit),
+            None => runner::run(&task),
+        };
+        match run.answer {
+            Ok(answer) => answer,
+            Err(failure) => failure.to_string(),
+        }
+    }
+}
+
+fn usage(command: &str) -> &'static str {
+    SHARED_COMMANDS
+        .iter()
+        .find(|shared| shared.name == command)
+        .map_or("", |shared| shared.usage)
+}
+
+// Reads commands from stdin until it runs out or someone types `quit`.
+pub fn repl(day: usize, path: &str, timeout: Option<Duration>) -> Summary {
+    let mut summary = Summary::default();
+    let session = match Session::load(day, path, timeout) {
+        Ok(session) => session,
+        Err(failure) => {
+            eprintln!("{}: {}", path, failure);
+            if let Failure::Parse(err) = &failure {
+                if let Some(location) = err.location() {
+                    eprintln!("{}", location.render(path));
+                }
+            }
+            summary.fail(format!("Day {}", day), (&failure).into());
+            return summary;
+        }
+    };
+
+    println!("Loaded {} for day {}. Type `help` for commands.", path, day);
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            break;
+        };
+        match session.respond(&line) {
+            Some(response) if response.is_empty() => (),
+            Some(response) => println!("{}", response),
+            None => break,
+        }
+    }
+
+    summary.pass();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Session;
+    use std::time::Duration;
+    use std::{env, fs};
+
+    fn session(day: usize, input: &str) -> Session {
+        session_with_timeout(day, input, None)
+    }
+
+    fn session_with_timeout(day: usize, input: &str, timeout: Option<Duration>) -> Session {
+        let path = env::temp_dir().join(format!("aoc-repl-{}-{}", day, std::process::id()));
+        fs::write(&path, input).unwrap();
+        let session = Session::load(day, &path.to_string_lossy(), timeout);
+        fs::remove_file(&path).unwrap();
+        session.ok().unwrap()
+    }
+
+    #[test]
+    fn answers_commands() {
+        let session = session(
+            8,
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+        );
+        assert_eq!(session.respond("solve 1").unwrap(), "6");
+        assert_eq!(
+            session.respond("step 3").unwrap(),
+            "Step 3: at BBB, going left next"
+        );
+        assert_eq!(session.respond("solve").unwrap(), "Usage: solve PART");
+        assert_eq!(
+            session.respond("solve 3").unwrap(),
+            "There's no solution for day 8, part 3"
+        );
+        assert_eq!(
+            session.respond("dance").unwrap(),
+            "Unknown command 'dance', try `help`"
+        );
+        assert!(session.respond("help").unwrap().contains("step N"));
+        assert!(session.respond("show").unwrap().contains("\"AAA\""));
+        assert_eq!(session.respond("   ").unwrap(), "");
+        assert_eq!(session.respond("quit"), None);
+    }
+
+    #[test]
+    fn solves_with_a_timeout() {
+        let input = "1 2 3\n";
+        let session = session_with_timeout(9, input, Some(Duration::from_secs(5)));
+        assert_eq!(session.respond("solve 1").unwrap(), "4");
+        assert_eq!(session.respond("solve 2").unwrap(), "0");
+    }
+}
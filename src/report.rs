@@ -0,0 +1,239 @@
+use rs_advent_of_code_2023::error::{self, Category};
+use rs_advent_of_code_2023::runner::{Failure, Run, Task};
+use std::str::FromStr;
+
+// How results get printed. Text is for people; JSON (one object per line) and CSV are for
+// anything that wants to read them back in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+pub const CSV_HEADER: &str = "day,part,input,answer,status,error,parse_ns,solve_ns";
+
+impl Format {
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Self::Csv => Some(CSV_HEADER),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, task: &Task, run: &Run) -> String {
+        match self {
+            Self::Text => render_text(task, run),
+            Self::Json => render_json(task, run),
+            Self::Csv => render_csv(task, run),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+fn render_text(task: &Task, run: &Run) -> String {
+    let name = task.name();
+    let elapsed_millis = run.elapsed().as_millis();
+    match &run.answer {
+        Ok(answer) if run.cached => format!("{}: {} (cached)", name, answer),
+        Ok(answer) => format!("{}: {} (in {} ms)", name, answer, elapsed_millis),
+        Err(err @ Failure::Solve(_)) => {
+            format!("{}: {} (in {} ms, if you care)", name, err, elapsed_millis)
+        }
+        // Point at exactly where the input went wrong, like a compiler would.
+        Err(failure @ Failure::Parse(err)) => match err.location() {
+            Some(location) => format!(
+                "{}: {}: {}\n{}",
+                name,
+                match err.category() {
+                    Category::InvalidPuzzle => "Invalid puzzle",
+                    _ => "Couldn't parse input",
+                },
+                error::chain(err.as_ref()),
+                location.render(task.input.name())
+            ),
+            None => format!("{}: {}", name, failure),
+        },
+        Err(err) => format!("{}: {}", name, err),
+    }
+}
+
+fn render_json(task: &Task, run: &Run) -> String {
+    let (answer, error) = match &run.answer {
+        Ok(answer) => (json_string(answer), String::from("null")),
+        Err(err) => (String::from("null"), json_string(&error_message(err))),
+    };
+
+    format!(
+        "{{\"day\":{},\"part\":{},\"input\":{},\"answer\":{},\"status\":\"{}\",\"error\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+        task.puzzle.day(),
+        task.puzzle.part(),
+        json_string(task.input.name()),
+        answer,
+        run.status(),
+        error,
+        run.parse_time.as_nanos(),
+        run.solve_time.as_nanos()
+    )
+}
+
+fn render_csv(task: &Task, run: &Run) -> String {
+    let (answer, error) = match &run.answer {
+        Ok(answer) => (csv_field(answer), String::new()),
+        Err(err) => (String::new(), csv_field(&error_message(err))),
+    };
+
+    format!(
+        "{},{},{},{},{},{},{},{}",
+        task.puzzle.day(),
+        task.puzzle.part(),
+        csv_field(task.input.name()),
+        answer,
+        run.status(),
+        error,
+        run.parse_time.as_nanos(),
+        run.solve_time.as_nanos()
+    )
+}
+
+// The status already says what kind of failure it was, so skip the "Couldn't parse input:" bit.
+fn error_message(failure: &Failure) -> String {
+    match failure {
+        Failure::Io(err) => err.to_string(),
+        Failure::Parse(err) | Failure::Solve(err) => match err.location() {
+            Some(location) => format!("{} ({})", error::chain(err.as_ref()), location),
+            None => error::chain(err.as_ref()),
+        },
+        Failure::Timeout(limit) => format!("Took longer than {:?}", limit),
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if control.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            char => escaped.push(char),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, json_string, Format};
+    use rs_advent_of_code_2023::registry;
+    use rs_advent_of_code_2023::runner::{Failure, Input, Run, Task};
+    use std::io;
+    use std::time::Duration;
+
+    fn task(input: &str) -> Task {
+        Task {
+            puzzle: registry::find(5, 2).unwrap(),
+            input: Input::File(String::from(input)),
+        }
+    }
+
+    fn run(answer: Result<String, Failure>) -> Run {
+        Run {
+            answer,
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_nanos(2500),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert_eq!("xml".parse::<Format>(), Err(()));
+    }
+
+    #[test]
+    fn renders_json() {
+        let result = Format::Json.render(&task("inputs/day5.txt"), &run(Ok(String::from("46"))));
+        assert_eq!(
+            result,
+            "{\"day\":5,\"part\":2,\"input\":\"inputs/day5.txt\",\"answer\":\"46\",\"status\":\"ok\",\"error\":null,\"parse_ns\":1500,\"solve_ns\":2500}"
+        );
+    }
+
+    #[test]
+    fn renders_csv_errors() {
+        let failure = Failure::Io(io::Error::new(io::ErrorKind::NotFound, "gone, missing"));
+        let result = Format::Csv.render(&task("inputs/day5.txt"), &run(Err(failure)));
+        assert_eq!(
+            result,
+            "5,2,inputs/day5.txt,,io_error,\"gone, missing\",1500,2500"
+        );
+    }
+
+    #[test]
+    fn names_unusual_inputs() {
+        let answer = || run(Ok(String::from("46")));
+        assert_eq!(
+            Format::Text.render(&task("inputs/day5.txt"), &answer()),
+            "Day 5, Part 2: 46 (in 0 ms)"
+        );
+        assert_eq!(
+            Format::Text.render(&task("alice/day5.txt"), &answer()),
+            "Day 5, Part 2 (alice/day5.txt): 46 (in 0 ms)"
+        );
+    }
+
+    #[test]
+    fn marks_cached_answers() {
+        let mut cached = run(Ok(String::from("46")));
+        cached.cached = true;
+        assert_eq!(
+            Format::Text.render(&task("inputs/day5.txt"), &cached),
+            "Day 5, Part 2: 46 (cached)"
+        );
+        assert!(Format::Csv
+            .render(&task("inputs/day5.txt"), &cached)
+            .contains(",cached,"));
+    }
+
+    #[test]
+    fn escapes_json() {
+        assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn escapes_csv() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
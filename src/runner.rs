@@ -0,0 +1,513 @@
+use crate::cache::{Cache, Key};
+use crate::error::{self, Category, PuzzleError};
+use crate::solution::{Example, Puzzle};
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// One puzzle to run against one input.
+#[derive(Clone)]
+pub struct Task {
+    pub puzzle: &'static dyn Puzzle,
+    pub input: Input,
+}
+
+// Where a puzzle's input comes from. Stdin can only be read once, so it gets slurped up front
+// and shared between every puzzle that wants it. Examples are built in and named "example N".
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    File(String),
+    Stdin(Arc<[u8]>),
+    Example(String, &'static Example),
+}
+
+// What happened when a puzzle was run against one input file. An answer that came out of the
+// cache took no time at all, as far as the timings are concerned.
+pub struct Run {
+    pub answer: Result<String, Failure>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub cached: bool,
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    Io(io::Error),
+    Parse(Box<dyn PuzzleError>),
+    Solve(Box<dyn PuzzleError>),
+    Timeout(Duration),
+}
+
+impl Task {
+    // Only bother saying which input it was when it's not the usual one.
+    pub fn name(&self) -> String {
+        match &self.input {
+            Input::File(path) if *path == self.puzzle.input_path() => self.puzzle.name(),
+            input => format!("{} ({})", self.puzzle.name(), input.name()),
+        }
+    }
+}
+
+impl Input {
+    // Turns the paths from the command line into inputs. `-` means stdin, and a directory means
+    // every file directly inside it, in alphabetical order.
+    pub fn expand(paths: &[String]) -> io::Result<Vec<Input>> {
+        let mut inputs = Vec::<Input>::new();
+        let mut stdin: Option<Arc<[u8]>> = None;
+
+        for path in paths {
+            if path == "-" {
+                if stdin.is_none() {
+                    let mut buf = Vec::new();
+                    io::stdin().read_to_end(&mut buf)?;
+                    stdin = Some(buf.into());
+                }
+                inputs.push(Input::Stdin(stdin.clone().unwrap()));
+            } else if fs::metadata(path)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path, err)))?
+                .is_dir()
+            {
+                let mut files = fs::read_dir(path)?
+                    .map(|entry| Ok(entry?.path()))
+                    .collect::<io::Result<Vec<_>>>()?;
+                files.retain(|file| file.is_file());
+                files.sort();
+                inputs.extend(
+                    files
+                        .into_iter()
+                        .map(|file| Input::File(file.to_string_lossy().into_owned())),
+                );
+            } else {
+                inputs.push(Input::File(path.clone()));
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    pub fn examples(puzzle: &dyn Puzzle) -> Vec<Input> {
+        puzzle
+            .examples()
+            .iter()
+            .enumerate()
+            .map(|(i, example)| Input::Example(format!("example {}", i + 1), example))
+            .collect()
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::File(path) => path,
+            Self::Stdin(_) => "-",
+            Self::Example(name, _) => name,
+        }
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            Self::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Self::Stdin(buf) => Ok(Box::new(&buf[..])),
+            Self::Example(_, example) => Ok(Box::new(example.input.as_bytes())),
+        }
+    }
+}
+
+pub fn run(task: &Task) -> Run {
+    let mut parse_time = Duration::ZERO;
+    let mut solve_time = Duration::ZERO;
+
+    let answer = task
+        .input
+        .open()
+        .map_err(Failure::Io)
+        .and_then(|mut reader| {
+            let start_time = Instant::now();
+            let input = panic::catch_unwind(AssertUnwindSafe(|| task.puzzle.parse(&mut reader)));
+            parse_time = start_time.elapsed();
+            input.map_err(panicked)?.map_err(Failure::Parse)
+        })
+        .and_then(|input| {
+            let start_time = Instant::now();
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| task.puzzle.solve(input)));
+            solve_time = start_time.elapsed();
+            answer.map_err(panicked)?.map_err(Failure::Solve)
+        });
+
+    Run {
+        answer,
+        parse_time,
+        solve_time,
+        cached: false,
+    }
+}
+
+// A puzzle that panics shouldn't take the rest of the run down with it. Nothing's shared between
+// puzzles, so there's no broken state left behind to worry about.
+fn panicked(_: Box<dyn Any + Send>) -> Failure {
+    Failure::Solve(Box::new(error::Panicked))
+}
+
+// Hands back the cached answer if there is one, and otherwise runs the task with `fresh` and
+// remembers the answer for next time. Failures aren't cached, since whatever went wrong might
+// not go wrong again.
+pub fn run_cached<F: FnOnce(&Task) -> Run>(task: &Task, cache: &Cache, fresh: F) -> Run {
+    let input = task.input.open().and_then(|mut reader| {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    });
+    // Let the real run report that the input couldn't be read.
+    let Ok(input) = input else {
+        return fresh(task);
+    };
+
+    let key = Key::new(task.puzzle, &input);
+    if let Some(answer) = cache.get(&key) {
+        return Run {
+            answer: Ok(answer),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            cached: true,
+        };
+    }
+
+    let run = fresh(task);
+    if let Ok(answer) = &run.answer {
+        cache.insert(key, answer.clone());
+    }
+    run
+}
+
+// Runs the task on its own thread and gives up waiting after `limit`. There's no way to stop a
+// thread from the outside, so a puzzle that's stuck in a loop keeps spinning in the background
+// until the process exits, but at least it doesn't hold everything else up.
+pub fn run_with_timeout(task: &Task, limit: Duration) -> Run {
+    let (sender, receiver) = mpsc::channel::<Run>();
+    let task = task.clone();
+    let start_time = Instant::now();
+    thread::spawn(move || {
+        // Nobody's listening anymore if we timed out, and that's fine.
+        let _ = sender.send(run(&task));
+    });
+
+    let (answer, solve_time) = match receiver.recv_timeout(limit) {
+        Ok(result) => return result,
+        Err(RecvTimeoutError::Timeout) => (Failure::Timeout(limit), limit),
+        // `run` catches panics in the puzzle itself, so this is only if something else fell over
+        // on the way. Either way it didn't take the whole limit.
+        Err(RecvTimeoutError::Disconnected) => (
+            Failure::Solve(Box::new(error::Panicked)),
+            start_time.elapsed(),
+        ),
+    };
+
+    // We can't tell whether it got stuck parsing or solving, so it all counts as solving.
+    Run {
+        answer: Err(answer),
+        parse_time: Duration::ZERO,
+        solve_time,
+        cached: false,
+    }
+}
+
+// Runs every task on up to `jobs` threads, but always hands the results to `report` in the same
+// order as `tasks` so the output doesn't shuffle around between runs. Each run is timed on the
+// thread that does it, so the timings only cover that puzzle.
+pub fn run_all<F: FnMut(&Task, Run)>(
+    tasks: &[Task],
+    jobs: usize,
+    timeout: Option<Duration>,
+    cache: Option<&Cache>,
+    mut report: F,
+) {
+    let run_fresh = |task: &Task| match timeout {
+        Some(limit) => run_with_timeout(task, limit),
+        None => run(task),
+    };
+    let run_one = |task: &Task| match cache {
+        Some(cache) => run_cached(task, cache, run_fresh),
+        None => run_fresh(task),
+    };
+
+    if jobs <= 1 {
+        for task in tasks {
+            report(task, run_one(task));
+        }
+        return;
+    }
+
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel::<(usize, Run)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let sender = sender.clone();
+            let next_task = &next_task;
+            let run_one = &run_one;
+            scope.spawn(move || {
+                let mut index = next_task.fetch_add(1, Ordering::Relaxed);
+                while let Some(task) = tasks.get(index) {
+                    let result = run_one(task);
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                    index = next_task.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+        drop(sender);
+
+        // Results come back in whatever order they finish, so hold on to them until it's their turn.
+        let mut finished = (0..tasks.len()).map(|_| None).collect::<Vec<Option<Run>>>();
+        let mut next_report = 0;
+        for (index, result) in receiver {
+            finished[index] = Some(result);
+            while let Some(result) = finished.get_mut(next_report).and_then(Option::take) {
+                report(&tasks[next_report], result);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+impl Run {
+    pub fn elapsed(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+
+    pub fn status(&self) -> &'static str {
+        match &self.answer {
+            Ok(_) if self.cached => "cached",
+            Ok(_) => "ok",
+            Err(Failure::Io(_)) => "io_error",
+            Err(Failure::Parse(err) | Failure::Solve(err)) => match err.category() {
+                Category::Io => "io_error",
+                Category::Parse => "parse_error",
+                Category::InvalidPuzzle => "invalid_puzzle",
+                Category::Unsolvable => "solve_error",
+            },
+            Err(Failure::Timeout(_)) => "timeout",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Couldn't read file: {}", err),
+            // Which stage it failed in matters less than what kind of error it was. A read
+            // error halfway through parsing is still a read error.
+            Self::Parse(err) | Self::Solve(err) => {
+                let message = error::chain(err.as_ref());
+                match (err.category(), err.location()) {
+                    (Category::Io, _) => write!(f, "Couldn't read file: {}", message),
+                    (Category::Parse, Some(location)) => {
+                        write!(f, "Couldn't parse input: {} ({})", message, location)
+                    }
+                    (Category::Parse, None) => write!(f, "Couldn't parse input: {}", message),
+                    (Category::InvalidPuzzle, _) => write!(f, "Invalid puzzle: {}", message),
+                    (Category::Unsolvable, _) => write!(f, "Errored with {}", message),
+                }
+            }
+            Self::Timeout(limit) => write!(f, "TIMEOUT after {:?}", limit),
+        }
+    }
+}
+
+impl Error for Failure {}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, run_all, run_cached, run_with_timeout, Failure, Input, Task};
+    use crate::cache::Cache;
+    use crate::day1::State;
+    use crate::error::Panicked;
+    use crate::registry;
+    use crate::solution::{Example, Solution};
+    use std::time::Duration;
+    use std::{env, fs, thread};
+
+    #[test]
+    fn reports_in_task_order() {
+        let tasks = registry::PUZZLES
+            .iter()
+            .map(|puzzle| Task {
+                puzzle: *puzzle,
+                input: Input::File(puzzle.input_path()),
+            })
+            .collect::<Vec<_>>();
+
+        let mut reported = vec![];
+        run_all(&tasks, 4, None, None, |task, run| {
+            assert!(run.answer.is_ok());
+            reported.push(task.puzzle.name());
+        });
+
+        let expected = tasks
+            .iter()
+            .map(|task| task.puzzle.name())
+            .collect::<Vec<_>>();
+        assert_eq!(reported, expected);
+    }
+
+    #[test]
+    fn expands_directories_in_order() {
+        let dir = env::temp_dir().join(format!("aoc-expand-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("bob.txt"), "").unwrap();
+        fs::write(dir.join("alice.txt"), "").unwrap();
+
+        let inputs = Input::expand(&[dir.to_string_lossy().into_owned()]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names = inputs.iter().map(|input| input.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                dir.join("alice.txt").to_string_lossy(),
+                dir.join("bob.txt").to_string_lossy()
+            ]
+        );
+    }
+
+    #[test]
+    fn sorts_failures_by_category() {
+        let run_day = |day, input: &[u8]| {
+            run(&Task {
+                puzzle: registry::find(day, 1).unwrap(),
+                input: Input::Stdin(input.into()),
+            })
+        };
+
+        assert_eq!(run_day(6, b"Time: 7 1x5\n").status(), "parse_error");
+        assert_eq!(run_day(1, b"ab\n").status(), "invalid_puzzle");
+        assert_eq!(run_day(7, b"\xff\n").status(), "io_error");
+
+        // The cause is kept around instead of being flattened into one message.
+        let Err(Failure::Parse(err)) = run_day(6, b"Time: 7 1x5\n").answer else {
+            panic!("Expected a parse error");
+        };
+        assert_eq!(err.to_string(), "Bad number '1x5'");
+        assert!(err.source().is_some());
+    }
+
+    // Stands in for a solver with a bug in it.
+    struct Explodes;
+
+    impl Solution for Explodes {
+        const DAY: usize = 0;
+        const PART: usize = 1;
+        const TITLE: &'static str = "Explodes";
+        const EXAMPLES: &'static [Example] = &[];
+
+        type Input = State;
+        type Output = usize;
+        type Error = Panicked;
+
+        fn solve(input: Self::Input) -> Result<Self::Output, Self::Error> {
+            let lines = input.lines();
+            assert!(lines.iter().all(|line| line != "boom"), "Boom");
+            Ok(lines.len())
+        }
+    }
+
+    // Stands in for a solver stuck in a loop.
+    struct Stalls;
+
+    impl Solution for Stalls {
+        const DAY: usize = 0;
+        const PART: usize = 2;
+        const TITLE: &'static str = "Stalls";
+        const EXAMPLES: &'static [Example] = &[];
+
+        type Input = State;
+        type Output = usize;
+        type Error = Panicked;
+
+        fn solve(_: Self::Input) -> Result<Self::Output, Self::Error> {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    #[test]
+    fn survives_panicking_solvers() {
+        let task = |input: &[u8]| Task {
+            puzzle: &Explodes,
+            input: Input::Stdin(input.into()),
+        };
+        let tasks = [task(b"a\nboom\n"), task(b"a\n"), task(b"boom\n")];
+
+        let mut statuses = vec![];
+        run_all(&tasks, 2, None, None, |_, run| statuses.push(run.status()));
+        assert_eq!(statuses, ["solve_error", "ok", "solve_error"]);
+
+        let Err(failure) = run(&tasks[0]).answer else {
+            panic!("Expected a failure");
+        };
+        assert_eq!(failure.to_string(), "Errored with Panicked while solving");
+    }
+
+    #[test]
+    fn reuses_cached_answers() {
+        let path = env::temp_dir().join(format!("aoc-runner-cache-{}", std::process::id()));
+        let cache = Cache::load(&path).unwrap();
+        let task = |input: &str| Task {
+            puzzle: registry::find(6, 1).unwrap(),
+            input: Input::Stdin(input.as_bytes().into()),
+        };
+        let example = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let first = run_cached(&task(example), &cache, run);
+        assert!(!first.cached);
+        let second = run_cached(&task(example), &cache, |_| panic!("Should've been cached"));
+        assert_eq!(second.status(), "cached");
+        assert_eq!(second.answer.unwrap(), first.answer.unwrap());
+
+        // A different input is a different answer.
+        let other = run_cached(&task("Time: 7\nDistance: 9\n"), &cache, run);
+        assert!(!other.cached);
+    }
+
+    #[test]
+    fn times_out_on_endless_puzzles() {
+        let task = Task {
+            puzzle: &Stalls,
+            input: Input::Stdin(b"1\n"[..].into()),
+        };
+
+        let result = run_with_timeout(&task, Duration::from_millis(50));
+        assert_eq!(result.status(), "timeout");
+    }
+
+    #[test]
+    fn finishes_before_the_timeout() {
+        let task = Task {
+            puzzle: registry::find(8, 1).unwrap(),
+            input: Input::Stdin(b"L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"[..].into()),
+        };
+
+        let result = run_with_timeout(&task, Duration::from_secs(5));
+        assert_eq!(result.answer.unwrap(), "1");
+    }
+
+    #[test]
+    fn panics_dont_count_as_timeouts() {
+        let task = Task {
+            puzzle: &Explodes,
+            input: Input::Stdin(b"boom\n"[..].into()),
+        };
+
+        let result = run_with_timeout(&task, Duration::from_secs(5));
+        assert_eq!(result.status(), "solve_error");
+        assert!(result.solve_time < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,446 @@
+use crate::cli::Args;
+use crate::report;
+use crate::summary;
+use rs_advent_of_code_2023::error;
+use rs_advent_of_code_2023::registry;
+use rs_advent_of_code_2023::runner::{self, Failure, Input, Run, Task};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PORT: u16 = 2023;
+
+// Nobody's day 5 input is anywhere near this, so anything bigger is a mistake.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+const MAX_HEADER_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+// How long a client gets to finish sending its request before we hang up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    status: u16,
+    body: String,
+}
+
+// Answers `POST /day/{n}/part/{m}` with the input as the body, forever. Only listens on
+// localhost, and each connection gets its own thread so a slow puzzle doesn't hold up the rest.
+pub fn serve(args: &Args) -> ! {
+    let listener = match TcpListener::bind(("127.0.0.1", args.port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Couldn't listen on port {}: {}", args.port, err);
+            // Usually it's already taken, which `--port` fixes.
+            process::exit(summary::USAGE_EXIT_CODE.into());
+        }
+    };
+    let address = listener.local_addr().map(|address| address.to_string());
+    eprintln!(
+        "Listening on http://{}",
+        address.unwrap_or_else(|err| err.to_string())
+    );
+
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    scope.spawn(move || handle_connection(stream, args));
+                }
+                Err(err) => eprintln!("Couldn't accept a connection: {}", err),
+            }
+        }
+    });
+
+    // `incoming` never runs out.
+    unreachable!()
+}
+
+fn handle_connection(stream: TcpStream, args: &Args) {
+    let start_time = Instant::now();
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+
+    let mut reader = BufReader::new(&stream);
+    let (line, response) = match read_request(&mut reader) {
+        Ok(request) => (
+            format!("{} {}", request.method, request.path),
+            handle(&request, args),
+        ),
+        Err(response) => (String::from("(bad request)"), response),
+    };
+
+    eprintln!(
+        "{} -> {} (in {} ms)",
+        line,
+        response.status,
+        start_time.elapsed().as_millis()
+    );
+    // They might have given up and gone already, and there's nobody else to tell.
+    let _ = response.write_to(&mut &stream);
+}
+
+// Just enough HTTP/1.1 to read one request with a `Content-Length` body. No chunked encoding and
+// no keep-alive, since every response closes the connection anyway.
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(path), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Response::error(400, "Malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(Response::error(505, "Only HTTP/1.x is supported"));
+    }
+
+    let mut content_length = None;
+    for _ in 0..=MAX_HEADERS {
+        let header = read_line(reader)?;
+        if header.is_empty() {
+            let body = read_body(reader, content_length.unwrap_or(0))?;
+            return Ok(Request {
+                method: String::from(method),
+                path: String::from(path),
+                body,
+            });
+        }
+
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(Response::error(400, "Malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, "Invalid Content-Length"))?;
+            content_length = Some(length);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(411, "Send a Content-Length instead"));
+        }
+    }
+
+    Err(Response::error(431, "Too many headers"))
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String, Response> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_HEADER_LINE as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|_| Response::error(400, "Couldn't read request"))?;
+    if line.len() > MAX_HEADER_LINE {
+        return Err(Response::error(431, "Header too long"));
+    }
+    if line.pop() != Some(b'\n') {
+        return Err(Response::error(400, "Request ended early"));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+
+    String::from_utf8(line).map_err(|_| Response::error(400, "Headers aren't UTF-8"))
+}
+
+fn read_body(reader: &mut impl BufRead, length: usize) -> Result<Vec<u8>, Response> {
+    if length > MAX_BODY {
+        return Err(Response::error(413, "Input is too big"));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "Body is shorter than its Content-Length"))?;
+    Ok(body)
+}
+
+// Works out which puzzle the request is for and runs it on the body. Nothing here touches the
+// network, so it can be tested on its own.
+pub fn handle(request: &Request, args: &Args) -> Response {
+    let Some((day, part)) = route(&request.path) else {
+        return Response::error(404, "Not found, try POST /day/{n}/part/{m}");
+    };
+    if request.method != "POST" {
+        return Response::error(405, "Use POST, with the puzzle input as the body");
+    }
+    let puzzle = match registry::find(day, part) {
+        Some(puzzle) if args.includes(day, part) => puzzle,
+        _ => {
+            return Response::error(
+                404,
+                &format!("There's no solution for day {}, part {}", day, part),
+            )
+        }
+    };
+
+    // The body is already in memory, just like stdin is by the time a puzzle sees it.
+    let task = Task {
+        puzzle,
+        input: Input::Stdin(request.body.as_slice().into()),
+    };
+    let run = match args.timeout {
+        Some(limit) => runner::run_with_timeout(&task, limit),
+        None => runner::run(&task),
+    };
+
+    Response {
+        status: status_code(&run),
+        body: render(&task, &run),
+    }
+}
+
+// "/day/5/part/2" is day 5, part 2.
+fn route(path: &str) -> Option<(usize, usize)> {
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let rest = path.strip_prefix("/day/")?;
+    let (day, part) = rest.split_once("/part/")?;
+    Some((day.parse().ok()?, part.parse().ok()?))
+}
+
+fn status_code(run: &Run) -> u16 {
+    match run.status() {
+        "ok" | "cached" => 200,
+        "timeout" => 504,
+        "solve_error" => 500,
+        // Whatever else went wrong, it was the input's fault.
+        _ => 422,
+    }
+}
+
+// Like `--format json`, except the parse error's location gets its own object instead of being
+// tacked onto the message.
+fn render(task: &Task, run: &Run) -> String {
+    let (answer, error, location) = match &run.answer {
+        Ok(answer) => (report::json_string(answer), None, None),
+        Err(Failure::Parse(err) | Failure::Solve(err)) => (
+            String::from("null"),
+            Some(error::chain(err.as_ref())),
+            err.location(),
+        ),
+        Err(Failure::Io(err)) => (String::from("null"), Some(err.to_string()), None),
+        Err(Failure::Timeout(limit)) => (
+            String::from("null"),
+            Some(format!("Took longer than {:?}", limit)),
+            None,
+        ),
+    };
+    let error = error.map_or(String::from("null"), |error| report::json_string(&error));
+    let location = location.map_or(String::from("null"), |location| {
+        format!(
+            "{{\"line\":{},\"column\":{},\"width\":{},\"source\":{}}}",
+            location.line,
+            location.column,
+            location.width,
+            report::json_string(&location.source)
+        )
+    });
+
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"status\":\"{}\",\"error\":{},\"location\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+        task.puzzle.day(),
+        task.puzzle.part(),
+        answer,
+        run.status(),
+        error,
+        location,
+        run.parse_time.as_nanos(),
+        run.solve_time.as_nanos()
+    )
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", report::json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            504 => "Gateway Timeout",
+            505 => "HTTP Version Not Supported",
+            _ => "",
+        }
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let allow = if self.status == 405 {
+            "Allow: POST\r\n"
+        } else {
+            ""
+        };
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len() + 1,
+            allow,
+            self.body
+        )?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle, handle_connection, read_request, route, status_code, Request, Response};
+    use crate::cli::Args;
+    use rs_advent_of_code_2023::error::Panicked;
+    use rs_advent_of_code_2023::runner::{Failure, Run};
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use std::time::Duration;
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: String::from("POST"),
+            path: String::from(path),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn routes_paths() {
+        assert_eq!(route("/day/5/part/2"), Some((5, 2)));
+        assert_eq!(route("/day/12/part/1?verbose"), Some((12, 1)));
+        assert_eq!(route("/day/5"), None);
+        assert_eq!(route("/day/x/part/1"), None);
+        assert_eq!(route("/"), None);
+    }
+
+    #[test]
+    fn reads_requests() {
+        let raw =
+            "POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\n1abc2";
+        assert_eq!(
+            read_request(&mut raw.as_bytes()),
+            Ok(post("/day/1/part/1", "1abc2"))
+        );
+
+        let short = "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 50\r\n\r\n1abc2";
+        assert_eq!(read_request(&mut short.as_bytes()).unwrap_err().status, 400);
+        let chunked = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert_eq!(
+            read_request(&mut chunked.as_bytes()).unwrap_err().status,
+            411
+        );
+        let huge = "POST / HTTP/1.1\r\nContent-Length: 999999999\r\n\r\n";
+        assert_eq!(read_request(&mut huge.as_bytes()).unwrap_err().status, 413);
+        assert_eq!(
+            read_request(&mut &b"nonsense\r\n"[..]).unwrap_err().status,
+            400
+        );
+    }
+
+    #[test]
+    fn solves_puzzles() {
+        let response = handle(
+            &post("/day/1/part/1", "1abc2\npqr3stu8vwx\n"),
+            &Args::default(),
+        );
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .starts_with("{\"day\":1,\"part\":1,\"answer\":\"50\",\"status\":\"ok\",\"error\":null,\"location\":null,"));
+    }
+
+    #[test]
+    fn points_at_parse_errors() {
+        let response = handle(
+            &post("/day/2/part/1", "Game 1: 3 blue\nGame 2: 4 purple\n"),
+            &Args::default(),
+        );
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"status\":\"parse_error\""));
+        assert!(response.body.contains(
+            "\"location\":{\"line\":2,\"column\":9,\"width\":8,\"source\":\"Game 2: 4 purple\"}"
+        ));
+    }
+
+    #[test]
+    fn blames_the_solver_for_panics() {
+        let run = Run {
+            answer: Err(Failure::Solve(Box::new(Panicked))),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            cached: false,
+        };
+        assert_eq!(status_code(&run), 500);
+    }
+
+    #[test]
+    fn rejects_unknown_puzzles() {
+        let args = Args::default();
+        assert_eq!(handle(&post("/day/30/part/1", ""), &args).status, 404);
+        assert_eq!(handle(&post("/days", ""), &args).status, 404);
+
+        let mut get = post("/day/1/part/1", "");
+        get.method = String::from("GET");
+        assert_eq!(handle(&get, &args).status, 405);
+
+        // Only the selected puzzles are served.
+        let only_day_5 = Args::parse(["serve", "5"].iter().map(|arg| arg.to_string())).unwrap();
+        assert_eq!(
+            handle(&post("/day/1/part/1", "1abc2\n"), &only_day_5).status,
+            404
+        );
+    }
+
+    #[test]
+    fn writes_responses() {
+        let mut written = Vec::new();
+        Response::error(405, "Use POST")
+            .write_to(&mut written)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "HTTP/1.1 405 Method Not Allowed\r\nContent-Type: application/json\r\nContent-Length: 21\r\nAllow: POST\r\nConnection: close\r\n\r\n{\"error\":\"Use POST\"}\n"
+        );
+    }
+
+    #[test]
+    fn answers_over_tcp() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let body = "0 3 6 9 12 15\n";
+            write!(
+                stream,
+                "POST /day/9/part/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(stream, &Args::default());
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"18\""));
+    }
+}
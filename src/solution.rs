@@ -0,0 +1,94 @@
+use crate::error::PuzzleError;
+use crate::input::PuzzleInput;
+use std::any::Any;
+use std::fmt::Display;
+use std::io::BufRead;
+
+// Everything the runner needs to know about a single part of a single day.
+pub trait Solution {
+    const DAY: usize;
+    const PART: usize;
+    const TITLE: &'static str;
+    const EXAMPLES: &'static [Example];
+    // Bump this whenever a change to the solver could change its answer, so answers cached from
+    // the old version get ignored.
+    const VERSION: usize = 1;
+
+    type Input: PuzzleInput;
+    type Output: Display;
+    type Error: PuzzleError;
+
+    fn solve(input: Self::Input) -> Result<Self::Output, Self::Error>;
+}
+
+// An example input from the puzzle description and the answer it's supposed to give.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    pub answer: &'static str,
+}
+
+// `Solution` has associated types, so it can't be made into a trait object. This is the
+// type-erased version of it that the registry holds, which lets us loop over every puzzle
+// without knowing their input and output types. Anything that implements `Solution`
+// implements this for free.
+pub trait Puzzle: Sync {
+    fn day(&self) -> usize;
+    fn part(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn examples(&self) -> &'static [Example];
+    fn version(&self) -> usize;
+
+    fn name(&self) -> String {
+        format!("Day {}, Part {}", self.day(), self.part())
+    }
+
+    fn input_path(&self) -> String {
+        format!("inputs/day{}.txt", self.day())
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Box<dyn PuzzleError>>;
+    fn solve(&self, input: Box<dyn Any>) -> Result<String, Box<dyn PuzzleError>>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+    <S::Input as PuzzleInput>::Error: PuzzleError,
+{
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn part(&self) -> usize {
+        S::PART
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn version(&self) -> usize {
+        S::VERSION
+    }
+
+    fn parse(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>, Box<dyn PuzzleError>> {
+        let input =
+            S::Input::from_reader(reader).map_err(|err| Box::new(err) as Box<dyn PuzzleError>)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve(&self, input: Box<dyn Any>) -> Result<String, Box<dyn PuzzleError>> {
+        let input = input
+            .downcast::<S::Input>()
+            // Only ever handed what our own `parse` returned, so it's always the right type.
+            .expect("Input was parsed by a different puzzle!");
+        let answer = S::solve(*input).map_err(|err| Box::new(err) as Box<dyn PuzzleError>)?;
+        Ok(answer.to_string())
+    }
+}
@@ -0,0 +1,156 @@
+use rs_advent_of_code_2023::error::Category;
+use rs_advent_of_code_2023::runner::Failure;
+use std::fmt::Display;
+use std::process::ExitCode;
+
+// Everything that can go wrong with a single puzzle, from least to most serious. When several
+// puzzles fail for different reasons, the most serious one decides the exit code.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Problem {
+    MissingInput,
+    ParseError,
+    InvalidPuzzle,
+    SolveError,
+    Mismatch,
+    Timeout,
+}
+
+// 2 is bad command-line arguments. A puzzle that panics counts as a solve error, so the only
+// way to get 101 is a bug outside the puzzles themselves.
+pub const USAGE_EXIT_CODE: u8 = 2;
+
+impl Problem {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::MissingInput => 3,
+            Self::ParseError => 4,
+            Self::SolveError => 5,
+            Self::Mismatch => 6,
+            Self::Timeout => 7,
+            Self::InvalidPuzzle => 8,
+        }
+    }
+}
+
+impl From<&Failure> for Problem {
+    fn from(value: &Failure) -> Self {
+        match value {
+            Failure::Io(_) => Self::MissingInput,
+            Failure::Parse(err) | Failure::Solve(err) => match err.category() {
+                Category::Io => Self::MissingInput,
+                Category::Parse => Self::ParseError,
+                Category::InvalidPuzzle => Self::InvalidPuzzle,
+                Category::Unsolvable => Self::SolveError,
+            },
+            Failure::Timeout(_) => Self::Timeout,
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingInput => write!(f, "missing input"),
+            Self::ParseError => write!(f, "parse error"),
+            Self::InvalidPuzzle => write!(f, "invalid puzzle"),
+            Self::SolveError => write!(f, "solve error"),
+            Self::Mismatch => write!(f, "wrong answer"),
+            Self::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+// Keeps track of which puzzles failed over a whole run.
+#[derive(Debug, Default)]
+pub struct Summary {
+    total: usize,
+    failures: Vec<(String, Problem)>,
+}
+
+impl Summary {
+    pub fn pass(&mut self) {
+        self.total += 1;
+    }
+
+    pub fn fail(&mut self, name: String, problem: Problem) {
+        self.total += 1;
+        self.failures.push((name, problem));
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        self.failures
+            .iter()
+            .map(|(_, problem)| problem)
+            .max()
+            .map_or(ExitCode::SUCCESS, |problem| problem.exit_code().into())
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.failures.is_empty() {
+            return write!(f, "All {} puzzles succeeded.", self.total);
+        }
+
+        write!(
+            f,
+            "{} of {} puzzles failed: ",
+            self.failures.len(),
+            self.total
+        )?;
+        for (i, (name, problem)) in self.failures.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}: {}", name, problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Problem, Summary};
+    use std::process::ExitCode;
+
+    #[test]
+    fn succeeds_with_no_failures() {
+        let mut summary = Summary::default();
+        summary.pass();
+        summary.pass();
+        assert_eq!(summary.exit_code(), ExitCode::SUCCESS);
+        assert_eq!(summary.to_string(), "All 2 puzzles succeeded.");
+    }
+
+    #[test]
+    fn most_serious_problem_wins() {
+        let mut summary = Summary::default();
+        summary.fail(String::from("Day 1, Part 1"), Problem::ParseError);
+        summary.pass();
+        summary.fail(String::from("Day 5, Part 2"), Problem::Mismatch);
+        summary.fail(String::from("Day 8, Part 1"), Problem::MissingInput);
+        assert_eq!(
+            summary.exit_code(),
+            ExitCode::from(Problem::Mismatch.exit_code())
+        );
+        assert_eq!(
+            summary.to_string(),
+            "3 of 4 puzzles failed: Day 1, Part 1: parse error; Day 5, Part 2: wrong answer; Day 8, Part 1: missing input"
+        );
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let codes = [
+            Problem::MissingInput,
+            Problem::ParseError,
+            Problem::SolveError,
+            Problem::Mismatch,
+            Problem::Timeout,
+            Problem::InvalidPuzzle,
+        ]
+        .map(|problem| problem.exit_code());
+        assert_eq!(codes, [3, 4, 5, 6, 7, 8]);
+    }
+}
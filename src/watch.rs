@@ -0,0 +1,143 @@
+use crate::report::Format;
+use rs_advent_of_code_2023::runner::{self, Input, Task};
+use std::collections::HashMap;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Keeps an eye on a set of files by checking when they were last modified (and how big they
+// are, in case two saves land within the same tick of the clock). No inotify or anything like
+// it, just asking again every so often.
+pub struct Watcher {
+    files: Vec<(String, Option<Stamp>)>,
+}
+
+type Stamp = (SystemTime, u64);
+
+impl Watcher {
+    pub fn new(paths: Vec<String>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = stamp(&path);
+                (path, stamp)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    // Every file that's changed since the last time we asked. A file that's deleted or shows up
+    // counts as a change too.
+    pub fn changed(&mut self) -> Vec<String> {
+        let mut changed = vec![];
+        for (path, last) in self.files.iter_mut() {
+            let stamp = stamp(path);
+            if stamp != *last {
+                *last = stamp;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn stamp(path: &str) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// Runs the tasks once, then again whenever one of their input files changes, forever. Each
+// answer after the first is followed by how it differs from the last one.
+pub fn watch(tasks: &[Task], timeout: Option<Duration>) -> ! {
+    let mut paths = tasks
+        .iter()
+        .filter_map(|task| match &task.input {
+            Input::File(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+
+    let mut watcher = Watcher::new(paths);
+    let mut previous = HashMap::<String, String>::new();
+    run(tasks, timeout, &mut previous);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let changed = watcher.changed();
+        if changed.is_empty() {
+            continue;
+        }
+
+        println!();
+        for path in changed.iter() {
+            println!("{} changed", path);
+        }
+
+        let tasks = tasks
+            .iter()
+            .filter(|task| matches!(&task.input, Input::File(path) if changed.contains(path)))
+            .cloned()
+            .collect::<Vec<_>>();
+        run(&tasks, timeout, &mut previous);
+    }
+}
+
+fn run(tasks: &[Task], timeout: Option<Duration>, previous: &mut HashMap<String, String>) {
+    runner::run_all(tasks, 1, timeout, None, |task, run| {
+        println!("{}", Format::Text.render(task, &run));
+
+        let outcome = match &run.answer {
+            Ok(answer) => answer.clone(),
+            Err(err) => err.to_string(),
+        };
+        if let Some(diff) = diff(previous.get(&task.name()).map(String::as_str), &outcome) {
+            println!("{}", diff);
+        }
+        previous.insert(task.name(), outcome);
+    });
+}
+
+// How an answer (or error) changed since last time, like a one-line `diff`. Nothing to say the
+// first time round.
+fn diff(previous: Option<&str>, current: &str) -> Option<String> {
+    match previous {
+        None => None,
+        Some(previous) if previous == current => Some(String::from("  (no change)")),
+        Some(previous) => Some(format!("  - {}\n  + {}", previous, current)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Watcher};
+    use std::{env, fs};
+
+    #[test]
+    fn diffs_answers() {
+        assert_eq!(diff(None, "46"), None);
+        assert_eq!(diff(Some("46"), "46").unwrap(), "  (no change)");
+        assert_eq!(diff(Some("35"), "46").unwrap(), "  - 35\n  + 46");
+    }
+
+    #[test]
+    fn notices_changes() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let path_name = path.to_string_lossy().into_owned();
+        fs::write(&path, "1").unwrap();
+
+        let mut watcher = Watcher::new(vec![path_name.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // A different size counts even if the clock hasn't moved on.
+        fs::write(&path, "12").unwrap();
+        assert_eq!(watcher.changed(), vec![path_name.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path_name]);
+    }
+}
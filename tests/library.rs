@@ -0,0 +1,39 @@
+// Uses the puzzles the way another tool would, from outside the crate.
+
+use rs_advent_of_code_2023::day1::{self, vocabulary::Vocabulary};
+use rs_advent_of_code_2023::day5::{self, part2, Puzzle, Range};
+use rs_advent_of_code_2023::input::PuzzleInput;
+use rs_advent_of_code_2023::registry;
+
+#[test]
+fn solves_without_the_binary() {
+    let puzzle = Puzzle::from_reader(day5::EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(puzzle.seeds(), [79, 14, 55, 13]);
+    assert_eq!(puzzle.maps()[0].from(), "seed");
+    assert_eq!(
+        puzzle.maps()[0].entries()[0].source(),
+        &Range::checked_from_size(98, 2).unwrap()
+    );
+    assert_eq!(Range::checked_from_size(98, 0), None);
+    assert_eq!(Range::checked_from_size(isize::MAX, 2), None);
+    assert_eq!(Range::new(99, 98), None);
+
+    assert_eq!(part2::get_lowest_range_location(puzzle).unwrap(), 46);
+}
+
+#[test]
+fn finds_puzzles_by_day() {
+    let puzzle = registry::find(5, 2).unwrap();
+    let input = puzzle.parse(&mut day5::EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(puzzle.solve(input).unwrap(), "46");
+}
+
+#[test]
+fn reads_calibrations_in_other_languages() {
+    let state = day1::State::from_reader("siebenzwei\nDREIxNEUN\n".as_bytes()).unwrap();
+    let german = Vocabulary::load("german").unwrap().ignoring_case();
+    assert_eq!(
+        day1::part2::sum_calibration_values_with(&state, &german.calibrator()),
+        Ok(72 + 39)
+    );
+}